@@ -0,0 +1,144 @@
+//! A global allocator counting what each thread allocates, so that every parse and part is accounted for on its own,
+//! even when they run in parallel. It only counts once enabled, e.g. with --memory.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// per thread, the heap can shrink below where a task started if it frees what another thread allocated
+#[thread_local]
+static ALLOCS: Cell<u64> = Cell::new(0);
+#[thread_local]
+static BYTES: Cell<u64> = Cell::new(0);
+#[thread_local]
+static LIVE: Cell<i64> = Cell::new(0);
+#[thread_local]
+static PEAK: Cell<i64> = Cell::new(0);
+
+fn allocated(size: usize) {
+    ALLOCS.set(ALLOCS.get() + 1);
+    BYTES.set(BYTES.get() + size as u64);
+    LIVE.set(LIVE.get() + size as i64);
+    PEAK.set(PEAK.get().max(LIVE.get()));
+}
+
+fn freed(size: usize) {
+    LIVE.set(LIVE.get() - size as i64);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            allocated(layout.size());
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            allocated(layout.size());
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ENABLED.load(Ordering::Relaxed) {
+            freed(layout.size());
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// What a task allocated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub allocs: u64,
+    pub bytes: u64,
+    /// The most the heap grew by while the task ran
+    pub peak: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocs,
+            size(self.bytes),
+            size(self.peak)
+        )
+    }
+}
+
+/// Runs a task and counts what it allocates on this thread, none unless counting is enabled.
+pub fn measure<T>(task: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return (task(), None);
+    }
+    let (allocs, bytes, live) = (ALLOCS.get(), BYTES.get(), LIVE.get());
+    PEAK.set(live);
+    let done = task();
+    let usage = Usage {
+        allocs: ALLOCS.get() - allocs,
+        bytes: BYTES.get() - bytes,
+        peak: (PEAK.get() - live).max(0) as u64,
+    };
+    (done, Some(usage))
+}
+
+// e.g. 512 B, 3.4 KiB or 56.0 MiB
+fn size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1048575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_measure() {
+        enable();
+        // the allocations would be optimized away otherwise
+        let (len, usage) = measure(|| {
+            let mut v = black_box(Vec::<u8>::with_capacity(1000));
+            v.push(1);
+            let w = black_box(Box::new([0_u8; 100]));
+            drop(black_box(v));
+            black_box(w).len()
+        });
+        assert_eq!(len, 100);
+        let usage = usage.unwrap();
+        assert_eq!(usage.allocs, 2);
+        assert_eq!(usage.bytes, 1100);
+        assert_eq!(usage.peak, 1100);
+        assert_eq!(usage.to_string(), "2 allocs, 1.1 KiB, peak 1.1 KiB");
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(size(512), "512 B");
+        assert_eq!(size(3482), "3.4 KiB");
+        assert_eq!(size(56 * 1048576), "56.0 MiB");
+    }
+}
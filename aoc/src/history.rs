@@ -0,0 +1,247 @@
+use crate::output::Record;
+use anyhow::Context;
+use aoc_lib::Part;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A record of a past run, one per line of the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Past {
+    /// When the run started, in milliseconds since the epoch, it identifies the run
+    pub run: u64,
+    pub revision: String,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub alt: String,
+    pub sample: Option<String>,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+}
+
+impl Past {
+    fn new(run: u64, revision: &str, record: &Record) -> Self {
+        Self {
+            run,
+            revision: revision.to_string(),
+            year: record.year,
+            day: record.day,
+            part: match record.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            alt: record.alt.to_string(),
+            sample: record.sample.map(|s| s.to_string()),
+            answer: record.answer.as_ref().map(|answer| answer.to_string()),
+            parse_ns: record.parse_time.map(|d| d.as_nanos() as u64),
+            solve_ns: record.solve_time.map(|d| d.as_nanos() as u64),
+        }
+    }
+
+    // what a record is of, to find the same part in the baseline
+    fn key(&self) -> (u16, u8, u8, &str, Option<&str>) {
+        (
+            self.year,
+            self.day,
+            self.part,
+            &self.alt,
+            self.sample.as_deref(),
+        )
+    }
+
+    fn total(&self) -> Option<Duration> {
+        Some(Duration::from_nanos(self.parse_ns? + self.solve_ns?))
+    }
+}
+
+/// All the past runs, appended to with the records of the current one.
+pub struct History {
+    file: File,
+    run: u64,
+    revision: String,
+    baseline: Vec<Past>,
+}
+
+impl History {
+    /// Opens the history file and picks the baseline: the latest record of each part, among the runs at `revision` if
+    /// given. The revision is matched exactly, runs of uncommitted changes are only picked with e.g. `9f55c18-dirty`.
+    pub fn open(path: &Path, revision: Option<&str>) -> anyhow::Result<Self> {
+        let past = match std::fs::read_to_string(path) {
+            Ok(content) => content
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| {
+                    serde_json::from_str::<Past>(line).with_context(|| {
+                        format!("invalid history at {}:{}", path.display(), idx + 1)
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => {
+                return Err(e).with_context(|| format!("cannot read history {}", path.display()))
+            }
+        };
+        let mut baseline: Vec<Past> = vec![];
+        for p in past {
+            if revision.is_some_and(|rev| p.revision != rev) {
+                continue;
+            }
+            match baseline.iter_mut().find(|b| b.key() == p.key()) {
+                Some(b) if b.run <= p.run => *b = p,
+                Some(_) => {}
+                None => baseline.push(p),
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("cannot open history {}", path.display()))?;
+        let run = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Ok(Self {
+            file,
+            run,
+            revision: revision_of_head(),
+            baseline,
+        })
+    }
+
+    /// Whether there's anything to compare with.
+    pub fn has_baseline(&self) -> bool {
+        !self.baseline.is_empty()
+    }
+
+    pub fn add(&mut self, record: &Record) -> anyhow::Result<()> {
+        let past = Past::new(self.run, &self.revision, record);
+        writeln!(self.file, "{}", serde_json::to_string(&past)?)?;
+        Ok(())
+    }
+
+    /// Describes how a record differs from the baseline, given a threshold in percent for timings.
+    pub fn compare(&self, record: &Record, threshold: f64) -> Vec<String> {
+        let now = Past::new(self.run, &self.revision, record);
+        let before = match self.baseline.iter().find(|p| p.key() == now.key()) {
+            Some(before) => before,
+            None => return vec!["not in the baseline".to_string()],
+        };
+        compare(before, &now, threshold)
+    }
+}
+
+fn compare(before: &Past, now: &Past, threshold: f64) -> Vec<String> {
+    let mut changes = vec![];
+    if before.answer != now.answer {
+        changes.push(format!(
+            "answer changed from {} to {}",
+            before.answer.as_deref().unwrap_or("nothing"),
+            now.answer.as_deref().unwrap_or("nothing")
+        ));
+    }
+    if let (Some(before), Some(now)) = (before.total(), now.total()) {
+        let change = (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+        if change > threshold {
+            changes.push(format!(
+                "slower: {:?} -> {:?} (+{:.0}%)",
+                before, now, change
+            ));
+        }
+    }
+    changes
+}
+
+/// The history at the root of the repository the revisions are taken from, in the current directory outside of one.
+pub fn default_path() -> PathBuf {
+    git(&["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("history.jsonl")
+}
+
+// e.g.: 9f55c18 or 9f55c18-dirty, so runs of uncommitted changes aren't mistaken for the commit
+fn revision_of_head() -> String {
+    git(&["describe", "--always", "--dirty"]).unwrap_or_else(|| "unknown".to_string())
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn past(answer: &str, solve_ns: u64) -> Past {
+        Past {
+            run: 1,
+            revision: "abc".to_string(),
+            year: 2021,
+            day: 7,
+            part: 2,
+            alt: "Main".to_string(),
+            sample: None,
+            answer: Some(answer.to_string()),
+            parse_ns: Some(1000),
+            solve_ns: Some(solve_ns),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert!(compare(&past("12", 9000), &past("12", 9500), 10.0).is_empty());
+        assert_eq!(
+            compare(&past("12", 9000), &past("13", 11000), 10.0),
+            vec![
+                "answer changed from 12 to 13",
+                "slower: 10µs -> 12µs (+20%)"
+            ]
+        );
+        assert!(compare(&past("12", 9000), &past("12", 11000), 25.0).is_empty());
+    }
+
+    #[test]
+    fn test_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let runs = [
+            (1, "abc", 1),
+            (1, "abc", 2),
+            (2, "abc-dirty", 1),
+            (3, "def", 2),
+        ];
+        let lines = runs.iter().map(|&(run, revision, part)| {
+            let past = Past {
+                run,
+                revision: revision.to_string(),
+                part,
+                ..past("12", 9000)
+            };
+            serde_json::to_string(&past).unwrap() + "\n"
+        });
+        std::fs::write(&path, lines.collect::<String>()).unwrap();
+
+        let baseline = |revision| {
+            let history = History::open(&path, revision).unwrap();
+            let runs = history.baseline.iter().map(|p| (p.part, p.run));
+            runs.collect::<Vec<_>>()
+        };
+        assert_eq!(baseline(None), vec![(1, 2), (2, 3)]);
+        assert_eq!(baseline(Some("abc")), vec![(1, 1), (2, 1)]);
+        assert_eq!(baseline(Some("abc-dirty")), vec![(1, 2)]);
+        assert!(baseline(Some("ab")).is_empty());
+    }
+}
@@ -0,0 +1,812 @@
+#![feature(thread_local)] // for the counters of the allocator
+use clap::Parser;
+use std::collections::HashMap;
+use std::error::Error;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, SystemTime};
+
+use aoc_lib::trace::{Event, Filter};
+use aoc_lib::*;
+
+mod alloc;
+mod history;
+mod output;
+mod scaffold;
+mod serve;
+mod timeout;
+mod trace;
+mod visualize;
+use alloc::Counting;
+use history::History;
+use output::{cross_check, Format, Record, Report};
+use timeout::Timeout;
+use trace::Tracer;
+use visualize::Player;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(clap::ArgEnum, Clone, Copy)]
+enum Mode {
+    Sample,
+    Print,
+    Submit,
+    /// Runs all the alternates and flags the parts on which they disagree
+    Verify,
+    /// Compares answers and timings with a baseline run from the history
+    Compare,
+    /// Animates the days that have a visualization, in the terminal
+    Visualize,
+}
+
+#[derive(Clone)]
+enum Days {
+    Set(Vec<u8>),
+    Range(u8, u8),
+}
+
+impl std::str::FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((from, to)) = s.split_once("..") {
+            let from = u8::from_str(from).unwrap_or(1);
+            let to = u8::from_str(to).unwrap_or(25);
+            Ok(Days::Range(from, to))
+        } else if s.to_ascii_lowercase() == "all" {
+            Ok(Days::Range(1, 25))
+        } else {
+            Ok(Days::Set(
+                s.split(',')
+                    .map(u8::from_str)
+                    .collect::<Result<Vec<u8>, ParseIntError>>()?,
+            ))
+        }
+    }
+}
+
+impl IntoIterator for Days {
+    type Item = u8;
+
+    type IntoIter = std::vec::IntoIter<u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Days::Set(days) => days.into_iter(),
+            Days::Range(from, to) => (from..=to).collect::<Vec<u8>>().into_iter(),
+        }
+    }
+}
+
+// a number of seconds a timeout can be of, rather than panicking when it's turned into a duration
+fn seconds(s: &str) -> Result<(), String> {
+    match s.parse::<f64>() {
+        Ok(secs) if secs > 0.0 && secs < u64::MAX as f64 => Ok(()),
+        Ok(_) => Err("expected a positive number of seconds".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Generates the module of a new day with a sample test and declares it, examples are taken from the description cached by aocf
+    New { year: u16, day: u8 },
+    /// Prints the samples found in the description of a day cached by aocf, as a sample! to check and paste
+    Samples { year: u16, day: u8 },
+    /// Solves the inputs posted to a local HTTP server, e.g. {"year": 2021, "day": 7, "part": 1, "input": "16,1,2"},
+    /// answers with the same JSON as --format json, and gives up on parts after --timeout
+    Serve {
+        #[clap(long, default_value = "8080")]
+        port: u16,
+    },
+}
+
+#[derive(clap::Parser)]
+struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Year to run, e.g.: -y 2021, defaults to all years
+    #[clap(short, long)]
+    year: Option<u16>,
+
+    /// Day(s) to run, e.g.: -d 1 or -d 1,3 or -d 3..6 or -d ..4
+    #[clap(short, name = "days", default_value = "all")]
+    days: Days,
+
+    /// Puzzle part to run, e.g.: -p 2
+    #[clap(short, name = "parts")]
+    part: Option<Part>,
+
+    #[clap(arg_enum, default_value = "print")]
+    mode: Mode,
+
+    /// Alternate(s) to run, e.g.: --alt main --alt astar, defaults to all alternates
+    #[clap(
+        long = "alt",
+        name = "alternate",
+        multiple_occurrences(true),
+        number_of_values = 1
+    )]
+    alts: Vec<String>,
+
+    /// Directory to read inputs and answers from instead of fetching them, e.g.: inputs/2021/day07.txt and inputs/2021/day07.answers
+    #[clap(long, parse(from_os_str))]
+    inputs: Option<PathBuf>,
+
+    /// Input file to run a single day with, its answers are read from the same file with an answers extension, - reads stdin
+    #[clap(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// Answer(s) the input should give, one per part run in order, e.g.: -p 2 --expect 168 or --expect 37 --expect 168
+    #[clap(
+        long,
+        requires = "input",
+        multiple_occurrences(true),
+        number_of_values = 1
+    )]
+    expect: Vec<String>,
+
+    /// Output format, json and csv emit one record per day, part and alternate
+    #[clap(arg_enum, short, long, default_value = "text")]
+    format: Format,
+
+    /// Server to fetch inputs from and submit answers to instead of aocf, e.g. a local stand-in, with the session cookie in AOC_SESSION
+    #[clap(long)]
+    server: Option<String>,
+
+    /// File to record submissions in, answers that can't be right aren't submitted
+    #[clap(long, parse(from_os_str), default_value = "submissions.tsv")]
+    ledger: PathBuf,
+
+    /// File to append the answers and timings of each run to, defaults to history.jsonl at the root of the repository
+    #[clap(long, parse(from_os_str))]
+    history: Option<PathBuf>,
+
+    /// Revision of the runs to compare with, e.g. 9f55c18 or 9f55c18-dirty, defaults to the latest run of each part
+    #[clap(long)]
+    baseline: Option<String>,
+
+    /// Slowdown in percent above which a timing is reported as a regression
+    #[clap(long, default_value = "10")]
+    threshold: f64,
+
+    /// Seconds after which parsing or solving a part is given up on, e.g. --timeout 2.5, other parts and days still run.
+    /// A part given up on can't be stopped though: it keeps running until it's done, and no other part is started meanwhile
+    #[clap(long, validator = seconds)]
+    timeout: Option<f64>,
+
+    /// Counts the allocations of each parse and part, and how much the heap grew by at most
+    #[clap(long)]
+    memory: bool,
+
+    /// Number of days, parts and alternates to run in parallel, 0 for one per CPU, each is timed on its own thread
+    #[clap(short, long, default_value = "1")]
+    jobs: usize,
+
+    /// Frames per second to start visualizations at, + and - change it while playing
+    #[clap(long, default_value = "10")]
+    fps: f64,
+
+    /// File to write the events solvers emit to as JSON lines, e.g. each explode and split of day 18, they are logged at
+    /// debug level otherwise
+    #[clap(long, parse(from_os_str))]
+    trace: Option<PathBuf>,
+
+    /// Day(s) to trace, e.g.: --trace-days 16,18
+    #[clap(long, name = "trace days", default_value = "all")]
+    trace_days: Days,
+
+    /// Kind(s) of events to trace, e.g.: --trace-kind explode --trace-kind split, defaults to all kinds
+    #[clap(
+        long = "trace-kind",
+        name = "kind",
+        multiple_occurrences(true),
+        number_of_values = 1
+    )]
+    trace_kinds: Vec<String>,
+
+    #[clap(short, parse(from_occurrences))]
+    verbose: usize,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+
+    if opts.memory {
+        alloc::enable();
+    }
+    stderrlog::new()
+        .timestamp(stderrlog::Timestamp::Off)
+        .verbosity(opts.verbose)
+        .init()?;
+
+    if let Some(Command::New { year, day }) = opts.command {
+        let scaffold = scaffold::new_day(&std::env::current_dir()?, year, day)?;
+        println!("Created {}", scaffold.module.display());
+        println!("Declared day{} in {}", day, scaffold.lib.display());
+        match scaffold.examples {
+            Some(count) => println!("Added {} example(s) from the cached description", count),
+            None => println!("No cached description, the sample is empty"),
+        }
+        return Ok(());
+    }
+    if let Some(Command::Samples { year, day }) = opts.command {
+        let description = scaffold::cached_description(&std::env::current_dir()?, year, day)
+            .ok_or_else(|| format!("no cached description for day {} of {}", day, year))?;
+        let candidates = aoc_lib::puzzle::candidates(&description);
+        println!("{}", scaffold::sample_macro(day, &candidates));
+        return Ok(());
+    }
+
+    if let Some(Command::Serve { port }) = opts.command {
+        let timeout = Timeout::new(opts.timeout.map(Duration::from_secs_f64));
+        serve::serve(&registry(), port, &timeout)?;
+        return Ok(());
+    }
+
+    let parts: Vec<Part> = match opts.part {
+        None => vec![Part::One, Part::Two],
+        Some(part) => vec![part],
+    };
+
+    let registry = registry();
+    let years = match opts.year {
+        None => registry.years(),
+        Some(year) => vec![year],
+    };
+    if opts.input.is_some() && (years.len() != 1 || opts.days.clone().into_iter().count() != 1) {
+        return Err("--input can only be used with a single day and year".into());
+    }
+    // local inputs may be anyone's, or edited
+    if matches!(opts.mode, Mode::Submit) && (opts.input.is_some() || opts.inputs.is_some()) {
+        return Err("only answers to the puzzle input are submitted, not to --input(s)".into());
+    }
+    if !opts.expect.is_empty() && opts.expect.len() != parts.len() {
+        return Err(format!(
+            "--expect needs one answer per part, {} given",
+            opts.expect.len()
+        )
+        .into());
+    }
+
+    let mut steps = vec![];
+    for year in years {
+        steps.push(Step::Year(year));
+        for day in opts.days.clone() {
+            steps.push(Step::Day(day));
+            let all = registry.day(year, day).collect::<Vec<_>>();
+            let entries = all
+                .iter()
+                .copied()
+                .filter(|entry| {
+                    opts.alts.is_empty()
+                        || opts
+                            .alts
+                            .iter()
+                            .any(|alt| alt.eq_ignore_ascii_case(entry.alt))
+                })
+                .collect::<Vec<_>>();
+            match entries.first() {
+                None if !all.is_empty() => steps.push(Step::Note(format!(
+                    "  - no such alternate for day {}, available: {}",
+                    day,
+                    all.iter()
+                        .map(|entry| entry.alt)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))),
+                None => steps.push(Step::Note(format!("  - no solver for day {}", day))),
+                Some(entry) if matches!(opts.mode, Mode::Sample) && opts.input.is_none() => {
+                    let samples = entry.samples();
+                    if samples.is_empty() {
+                        steps.push(Step::Note(format!("  - no sample for day {}", day)));
+                    }
+                    for sample in samples {
+                        if samples.len() > 1 {
+                            steps.push(Step::Sample(sample.name));
+                        }
+                        // samples can be specific to one of the parts
+                        let parts = parts
+                            .iter()
+                            .copied()
+                            .filter(|&part| sample.answer(part).is_some())
+                            .collect::<Vec<_>>();
+                        let input = Box::new(*sample);
+                        steps.push(load(&entries, &parts, Some(sample.name), input));
+                    }
+                }
+                Some(entry) => {
+                    let input = load_input(entry, &opts);
+                    steps.push(load(&entries, &parts, None, input));
+                }
+            }
+            steps.push(Step::EndDay);
+        }
+    }
+
+    if let Mode::Visualize = opts.mode {
+        return visualize(&steps, &opts);
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(opts.jobs)
+        .build()?;
+    let mut submitter = Submitter {
+        backend: backend(&opts),
+        ledger: match opts.mode {
+            Mode::Submit => Ledger::open(&opts.ledger)?,
+            _ => Ledger::default(),
+        },
+    };
+    let history_path = opts.history.clone().unwrap_or_else(history::default_path);
+    let mut history = History::open(&history_path, opts.baseline.as_deref())?;
+    let mut tracer = match &opts.trace {
+        Some(path) => Some(Tracer::create(
+            path,
+            opts.trace_days.clone().into_iter().collect(),
+            opts.trace_kinds.clone(),
+        )?),
+        None => None,
+    };
+    let mut report = Report::new(opts.format, opts.verbose > 0);
+    if let Mode::Compare = opts.mode {
+        if !history.has_baseline() {
+            return Err("no run to compare with in the history".into());
+        }
+        report.note(match &opts.baseline {
+            Some(revision) => format!("Comparing with the latest runs at {}", revision),
+            None => "Comparing with the latest run of each part".to_string(),
+        });
+    }
+    pool.in_place_scope(|scope| {
+        report_steps(
+            &mut report,
+            &mut submitter,
+            &mut history,
+            tracer.as_mut(),
+            &steps,
+            &opts,
+            scope,
+        )
+    })?;
+    report.end_run();
+    if let Some(tracer) = &mut tracer {
+        tracer.flush()?;
+    }
+    Ok(())
+}
+
+// parses and animates each run in turn, notes are printed between animations
+fn visualize(steps: &[Step], opts: &Opts) -> Result<(), Box<dyn Error>> {
+    let mut player = Player::new(opts.fps);
+    for step in steps {
+        let run = match step {
+            Step::Note(note) => {
+                println!("{}", note.trim_start());
+                continue;
+            }
+            Step::Run(run) => run,
+            _ => continue,
+        };
+        for entry in &run.entries {
+            let parsed = match entry.parse(&run.input) {
+                Ok((parsed, _)) => parsed,
+                Err(e) => {
+                    println!("- cannot parse input for {:?}: {}", entry, e);
+                    continue;
+                }
+            };
+            for &part in &run.parts {
+                let title = format!("{:?} part {:?}", entry, part);
+                match entry.visualize(&parsed, part) {
+                    Some(frames) => {
+                        if !player.play(&title, frames)? {
+                            return Ok(());
+                        }
+                    }
+                    None => println!("- no visualization for {}", title),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// all the years this binary can run
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.merge(aoc_2021::registry());
+    registry
+}
+
+/// What to report, in order, while the runs are solved on the pool.
+enum Step<'a> {
+    Year(u16),
+    Day(u8),
+    Sample(&'static str),
+    Note(String),
+    Run(Run<'a>),
+    EndDay,
+}
+
+/// The alternates of a day to run on a loaded input.
+struct Run<'a> {
+    entries: Vec<&'a Entry>,
+    parts: Vec<Part>,
+    sample: Option<&'static str>,
+    input: String,
+    expected: Vec<Option<String>>,
+}
+
+impl Run<'_> {
+    // the first entry sharing the input of an entry, it parses the input for all of them
+    fn parser(&self, idx: usize) -> usize {
+        let entry = self.entries[idx];
+        self.entries
+            .iter()
+            .position(|other| entry.shares_input(other))
+            .unwrap_or(idx)
+    }
+
+    fn record(&self, idx: usize, part: usize) -> Record {
+        let mut record = Record::new(
+            self.entries[idx],
+            self.parts[part],
+            self.expected[part].clone(),
+        );
+        record.sample = self.sample;
+        record
+    }
+}
+
+fn load<'a>(
+    entries: &[&'a Entry],
+    parts: &[Part],
+    sample: Option<&'static str>,
+    mut input: Box<dyn Input>,
+) -> Step<'a> {
+    match input.load() {
+        Ok(loaded) => Step::Run(Run {
+            entries: entries.to_vec(),
+            parts: parts.to_vec(),
+            sample,
+            input: loaded,
+            expected: parts.iter().map(|&part| input.solution(part)).collect(),
+        }),
+        Err(e) => Step::Note(format!("  - cannot load input: {:#}", e)),
+    }
+}
+
+// a task result, keyed by step, entry and part, the part is none for parsing
+type Key = (usize, usize, Option<usize>);
+
+enum Done {
+    Parsed(Option<Duration>, Vec<Event>),
+    Solved(Option<Box<Record>>, Vec<Event>),
+}
+
+// parses each input once for all the alternates that share it, then solves every part in its own task
+fn spawn_run<'a>(
+    scope: &rayon::Scope<'a>,
+    step: usize,
+    run: &'a Run,
+    timeout: &Timeout,
+    filter: Option<Filter>,
+    tx: Sender<(Key, Done)>,
+) {
+    for idx in (0..run.entries.len()).filter(|&idx| run.parser(idx) == idx) {
+        let (tx, filter, timeout) = (tx.clone(), filter.clone(), timeout.clone());
+        scope.spawn(move |scope| {
+            let (parser, input) = (*run.entries[idx], run.input.clone());
+            let parse_filter = filter.clone();
+            let parsed = timeout.run(move || {
+                alloc::measure(|| {
+                    aoc_lib::trace::record(parse_filter.as_ref(), || parser.parse(&input))
+                })
+            });
+            let (parsed, parse_usage, events) = match parsed {
+                Ok(((parsed, events), usage)) => (Ok(parsed), usage, events),
+                Err(given_up) => (Err(given_up), None, vec![]),
+            };
+            let duration = match &parsed {
+                Ok(Ok((_, duration))) => Some(*duration),
+                _ => None,
+            };
+            let _ = tx.send(((step, idx, None), Done::Parsed(duration, events)));
+
+            for entry in (0..run.entries.len()).filter(|&other| run.parser(other) == idx) {
+                for part in 0..run.parts.len() {
+                    let key = (step, entry, Some(part));
+                    let mut record = run.record(entry, part);
+                    let (parsed, parse) = match &parsed {
+                        Ok(Ok((parsed, parse))) => (parsed.clone(), *parse),
+                        // parsing won't go any better for the next parts
+                        Ok(Err(e)) => {
+                            let record = (part == 0).then(|| {
+                                record.failed(e);
+                                record
+                            });
+                            let _ = tx.send((key, Done::Solved(record.map(Box::new), vec![])));
+                            continue;
+                        }
+                        Err(given_up) => {
+                            let record = (part == 0).then(|| {
+                                record.given_up(*given_up);
+                                record
+                            });
+                            let _ = tx.send((key, Done::Solved(record.map(Box::new), vec![])));
+                            continue;
+                        }
+                    };
+                    let (tx, filter, timeout) = (tx.clone(), filter.clone(), timeout.clone());
+                    scope.spawn(move |_| {
+                        let (solver, part) = (*run.entries[entry], run.parts[part]);
+                        let solved = timeout.run(move || {
+                            let solve = || metrics::collect(|| solver.solve_parsed(&parsed, part));
+                            let (((solved, metrics), events), usage) =
+                                alloc::measure(|| aoc_lib::trace::record(filter.as_ref(), solve));
+                            (solved, metrics, events, usage)
+                        });
+                        let events = match solved {
+                            Ok((Ok((solution, solve)), metrics, events, usage)) => {
+                                record.solved(solution, Timings { parse, solve });
+                                record.used(parse_usage, usage);
+                                record.metrics = metrics;
+                                events
+                            }
+                            // the events leading to a failure are the most telling
+                            Ok((Err(e), _, events, _)) => {
+                                record.failed(e);
+                                events
+                            }
+                            Err(given_up) => {
+                                record.given_up(given_up);
+                                vec![]
+                            }
+                        };
+                        let _ = tx.send((key, Done::Solved(Some(Box::new(record)), events)));
+                    });
+                }
+            }
+        });
+    }
+}
+
+// hands all the runs to the pool and reports their results in order as they come in
+fn report_steps<'a>(
+    report: &mut Report,
+    submitter: &mut Submitter,
+    history: &mut History,
+    mut tracer: Option<&mut Tracer>,
+    steps: &'a [Step],
+    opts: &Opts,
+    scope: &rayon::Scope<'a>,
+) -> anyhow::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let timeout = Timeout::new(opts.timeout.map(Duration::from_secs_f64));
+    for (idx, step) in steps.iter().enumerate() {
+        if let Step::Run(run) = step {
+            let filter = tracer
+                .as_ref()
+                .and_then(|tracer| tracer.filter(run.entries[0].day))
+                .cloned();
+            spawn_run(scope, idx, run, &timeout, filter, tx.clone());
+        }
+    }
+    drop(tx);
+
+    let mut done = HashMap::new();
+    let mut wait = |key: Key| -> anyhow::Result<Done> {
+        loop {
+            if let Some(result) = done.remove(&key) {
+                return Ok(result);
+            }
+            let (other, result) = rx.recv()?;
+            done.insert(other, result);
+        }
+    };
+
+    for (idx, step) in steps.iter().enumerate() {
+        match step {
+            Step::Year(year) => report.year(*year),
+            Step::Day(day) => report.day(*day),
+            Step::Sample(name) => report.sample(name),
+            Step::Note(note) => report.note(note),
+            Step::EndDay => report.end_day(),
+            Step::Run(run) => {
+                let mut records = vec![];
+                for (entry_idx, &entry) in run.entries.iter().enumerate() {
+                    if run.parser(entry_idx) == entry_idx {
+                        if let Done::Parsed(duration, events) = wait((idx, entry_idx, None))? {
+                            if let Some(duration) = duration {
+                                report.parsed(duration);
+                            }
+                            if let Some(tracer) = &mut tracer {
+                                tracer.write(entry, None, run.sample, &events)?;
+                            }
+                        }
+                    }
+                    for (part_idx, &part) in run.parts.iter().enumerate() {
+                        if let Done::Solved(record, events) =
+                            wait((idx, entry_idx, Some(part_idx)))?
+                        {
+                            if let Some(tracer) = &mut tracer {
+                                tracer.write(entry, Some(part), run.sample, &events)?;
+                            }
+                            if let Some(record) = record {
+                                records.push((entry, part, *record));
+                            }
+                        }
+                    }
+                }
+
+                if let Mode::Verify = opts.mode {
+                    for disagreement in cross_check(records.iter_mut().map(|(_, _, r)| r)) {
+                        report.note(format!("  - alternates disagree on {}", disagreement));
+                    }
+                }
+                for (entry, part, record) in records {
+                    report.start(entry, part);
+                    report.record(&record)?;
+                    history.add(&record)?;
+                    if let Mode::Compare = opts.mode {
+                        for change in history.compare(&record, opts.threshold) {
+                            report.note(format!("    {}", change));
+                        }
+                    }
+
+                    if let (Mode::Submit, Some(solution)) = (opts.mode, &record.answer) {
+                        submitter.submit(report, entry, part, solution.to_string())?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Submits answers to the backend, unless the ledger knows they can't be right.
+struct Submitter {
+    backend: Box<dyn Backend>,
+    ledger: Ledger,
+}
+
+impl Submitter {
+    fn submit(
+        &mut self,
+        report: &Report,
+        entry: &Entry,
+        part: Part,
+        solution: String,
+    ) -> anyhow::Result<()> {
+        let (year, day) = (entry.year, entry.day);
+        if self.backend.answer(year, day, part)?.is_some() {
+            report.note(format!(
+                "Solution for day {} part {:?} has already been accepted.",
+                day, part
+            ));
+        } else if let Err(refusal) =
+            self.ledger
+                .check(year, day, part, &solution, SystemTime::now())
+        {
+            report.note(format!(
+                "Not submitting solution {} for day {} part {:?}: {}",
+                solution, day, part, refusal
+            ));
+        } else {
+            report.note(format!(
+                "Submitting solution {} for day {} part {:?}",
+                solution, day, part
+            ));
+
+            let response = self.backend.submit(year, day, part, &solution)?;
+            self.ledger
+                .record(Submission::new(year, day, part, &solution, &response))?;
+            report.note(response);
+        }
+        Ok(())
+    }
+}
+
+fn backend(opts: &Opts) -> Box<dyn Backend> {
+    match &opts.server {
+        Some(url) => Box::new(Http::new(url, std::env::var("AOC_SESSION").ok())),
+        None => Box::new(Aocf),
+    }
+}
+
+/// An input with the answers given on the command line, instead of the ones it knows.
+struct Expected {
+    input: Box<dyn Input>,
+    answers: Vec<(Part, String)>,
+}
+
+impl Input for Expected {
+    fn load(&mut self) -> anyhow::Result<String> {
+        self.input.load()
+    }
+
+    fn solution(&self, part: Part) -> Option<String> {
+        self.answers
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.clone())
+    }
+}
+
+fn load_input(entry: &Entry, opts: &Opts) -> Box<dyn Input> {
+    if let Some(path) = &opts.input {
+        let input: Box<dyn Input> = match path.to_str() {
+            Some("-") => Box::new(StdinInput),
+            _ => Box::new(LocalInput::from_file(path)),
+        };
+        if opts.expect.is_empty() {
+            return input;
+        }
+        let parts = match opts.part {
+            None => vec![Part::One, Part::Two],
+            Some(part) => vec![part],
+        };
+        return Box::new(Expected {
+            input,
+            answers: parts.into_iter().zip(opts.expect.clone()).collect(),
+        });
+    }
+    match &opts.inputs {
+        Some(dir) => Box::new(LocalInput::new(dir, entry.year, entry.day)),
+        None => Box::new(RemoteInput::new(backend(opts), entry.year, entry.day)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use output::Verdict;
+
+    // the records of the parts of a run, by part
+    fn solve(run: &Run, timeout: &Timeout) -> Vec<Record> {
+        let (tx, rx) = mpsc::channel();
+        rayon::scope(|scope| spawn_run(scope, 0, run, timeout, None, tx));
+        let mut records = rx
+            .into_iter()
+            .filter_map(|(key, done)| match done {
+                Done::Solved(Some(record), _) => Some((key, *record)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        records.sort_by_key(|(key, _)| *key);
+        records.into_iter().map(|(_, record)| record).collect()
+    }
+
+    #[test]
+    fn test_timeout() {
+        let registry = timeout::test::stuck();
+        let run = |part| Run {
+            entries: registry.day(2021, 1).collect(),
+            parts: vec![part],
+            sample: None,
+            input: String::new(),
+            expected: vec![None],
+        };
+        let timeout = Timeout::new(Some(Duration::from_millis(20)));
+
+        let records = solve(&run(Part::One), &timeout);
+        assert_eq!(records[0].verdict, Verdict::Timeout);
+        assert_eq!(records[0].error.as_deref(), Some("timed out after 20ms"));
+
+        // part one is still running
+        let records = solve(&run(Part::Two), &timeout);
+        assert_eq!(records[0].verdict, Verdict::Timeout);
+        assert_eq!(
+            records[0].error.as_deref(),
+            Some("not started, a task that timed out is still running")
+        );
+        assert_eq!(records[0].answer, None);
+
+        let records = solve(&run(Part::Two), &Timeout::new(None));
+        assert_eq!(records[0].answer, Some(Answer::Number(2)));
+    }
+}
@@ -0,0 +1,500 @@
+use crate::alloc::Usage;
+use crate::timeout::GivenUp;
+use aoc_lib::metrics::Metrics;
+use aoc_lib::{Answer, Entry, Part, Timings};
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::time::Duration;
+use termion::{color, style};
+
+#[derive(clap::ArgEnum, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    Unknown,
+    Failed,
+    /// No answer is known but the alternates disagree
+    Mismatch,
+    /// Given up on after the timeout, or not started while a part given up on was still running
+    Timeout,
+    /// The puzzle doesn't have this part
+    Absent,
+}
+
+/// The outcome of running one part of one alternate.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    #[serde(serialize_with = "part_number")]
+    pub part: Part,
+    pub alt: &'static str,
+    /// The name of the sample, when running samples
+    pub sample: Option<&'static str>,
+    #[serde(serialize_with = "displayed")]
+    pub answer: Option<Answer>,
+    pub expected: Option<String>,
+    pub verdict: Verdict,
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    pub parse_time: Option<Duration>,
+    #[serde(rename = "solve_ns", serialize_with = "nanos")]
+    pub solve_time: Option<Duration>,
+    pub error: Option<String>,
+    // flat for csv, see `used`
+    pub parse_allocs: Option<u64>,
+    pub parse_alloc_bytes: Option<u64>,
+    pub parse_peak_bytes: Option<u64>,
+    pub solve_allocs: Option<u64>,
+    pub solve_alloc_bytes: Option<u64>,
+    pub solve_peak_bytes: Option<u64>,
+    /// What the solver counted while solving, as a summary
+    #[serde(serialize_with = "summary")]
+    pub metrics: Metrics,
+}
+
+impl Record {
+    pub fn new(entry: &Entry, part: Part, expected: Option<String>) -> Self {
+        Self {
+            year: entry.year,
+            day: entry.day,
+            part,
+            alt: entry.alt,
+            sample: None,
+            answer: None,
+            expected,
+            verdict: Verdict::Unknown,
+            parse_time: None,
+            solve_time: None,
+            error: None,
+            parse_allocs: None,
+            parse_alloc_bytes: None,
+            parse_peak_bytes: None,
+            solve_allocs: None,
+            solve_alloc_bytes: None,
+            solve_peak_bytes: None,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Accounts for what was allocated while parsing and solving, when allocations are counted.
+    pub fn used(&mut self, parse: Option<Usage>, solve: Option<Usage>) {
+        self.parse_allocs = parse.map(|usage| usage.allocs);
+        self.parse_alloc_bytes = parse.map(|usage| usage.bytes);
+        self.parse_peak_bytes = parse.map(|usage| usage.peak);
+        self.solve_allocs = solve.map(|usage| usage.allocs);
+        self.solve_alloc_bytes = solve.map(|usage| usage.bytes);
+        self.solve_peak_bytes = solve.map(|usage| usage.peak);
+    }
+
+    fn usage(&self) -> Option<(Usage, Usage)> {
+        let parse = Usage {
+            allocs: self.parse_allocs?,
+            bytes: self.parse_alloc_bytes?,
+            peak: self.parse_peak_bytes?,
+        };
+        let solve = Usage {
+            allocs: self.solve_allocs?,
+            bytes: self.solve_alloc_bytes?,
+            peak: self.solve_peak_bytes?,
+        };
+        Some((parse, solve))
+    }
+
+    pub fn solved(&mut self, answer: Answer, timings: Timings) {
+        self.parse_time = Some(timings.parse);
+        self.solve_time = Some(timings.solve);
+        if answer.is_none() {
+            self.verdict = Verdict::Absent;
+            return;
+        }
+        self.verdict = match &self.expected {
+            Some(expected) if answer.matches(expected) => Verdict::Correct,
+            Some(_) => Verdict::Incorrect,
+            None => Verdict::Unknown,
+        };
+        self.answer = Some(answer);
+    }
+
+    pub fn failed(&mut self, error: impl Display) {
+        self.verdict = Verdict::Failed;
+        self.error = Some(error.to_string());
+    }
+
+    pub fn given_up(&mut self, given_up: GivenUp) {
+        self.verdict = Verdict::Timeout;
+        self.error = Some(given_up.to_string());
+    }
+}
+
+/// Flags the records of the parts on which the alternates disagree, returns a description of each disagreement.
+pub fn cross_check<'r>(records: impl IntoIterator<Item = &'r mut Record>) -> Vec<String> {
+    let mut records = records.into_iter().collect::<Vec<_>>();
+    let mut disagreements = vec![];
+    for part in [Part::One, Part::Two] {
+        let mut solved = records
+            .iter_mut()
+            .filter(|record| record.part == part && record.answer.is_some())
+            .collect::<Vec<_>>();
+        let first = match solved.first().and_then(|record| record.answer.as_ref()) {
+            Some(first) => first.to_string(),
+            None => continue,
+        };
+        // equivalent answers can be of different kinds, e.g. a number returned as text
+        if solved
+            .iter()
+            .all(|record| matches!(&record.answer, Some(answer) if answer.matches(&first)))
+        {
+            continue;
+        }
+        let answers = solved
+            .iter()
+            .map(|record| format!("{} {}", record.alt, answer_text(record)))
+            .collect::<Vec<_>>();
+        disagreements.push(format!("part {:?}: {}", part, answers.join(", ")));
+        for record in solved.iter_mut() {
+            if record.verdict == Verdict::Unknown {
+                record.verdict = Verdict::Mismatch;
+            }
+        }
+    }
+    disagreements
+}
+
+fn part_number<S: Serializer>(part: &Part, serializer: S) -> Result<S::Ok, S::Error> {
+    match part {
+        Part::One => serializer.serialize_u8(1),
+        Part::Two => serializer.serialize_u8(2),
+    }
+}
+
+fn summary<S: Serializer>(metrics: &Metrics, serializer: S) -> Result<S::Ok, S::Error> {
+    match metrics.is_empty() {
+        true => serializer.serialize_none(),
+        false => serializer.collect_str(metrics),
+    }
+}
+
+fn displayed<S: Serializer>(answer: &Option<Answer>, serializer: S) -> Result<S::Ok, S::Error> {
+    match answer {
+        Some(answer) => serializer.collect_str(answer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn answer_text(record: &Record) -> String {
+    record
+        .answer
+        .as_ref()
+        .map(Answer::to_string)
+        .unwrap_or_default()
+}
+
+fn nanos<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serializer.serialize_u64(duration.as_nanos() as u64),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Writes records in the chosen format, the text format also has headers and progress.
+pub struct Report {
+    format: Format,
+    // metrics are detailed one per line
+    verbose: bool,
+    csv: csv::Writer<io::Stdout>,
+    // records and timings of the current day, then of the whole run
+    day: (usize, Timings),
+    run: (usize, Timings),
+}
+
+impl Report {
+    pub fn new(format: Format, verbose: bool) -> Self {
+        Self {
+            format,
+            verbose,
+            csv: csv::Writer::from_writer(io::stdout()),
+            day: Default::default(),
+            run: Default::default(),
+        }
+    }
+
+    pub fn year(&self, year: u16) {
+        if self.format == Format::Text {
+            println!("AoC - {}", year);
+        }
+    }
+
+    pub fn day(&self, day: u8) {
+        if self.format == Format::Text {
+            println!("Day {}", day);
+        }
+    }
+
+    /// Any other message, sent to stderr for the machine-readable formats.
+    pub fn note(&self, message: impl Display) {
+        match self.format {
+            Format::Text => println!("{}", message),
+            _ => eprintln!("{}", message),
+        }
+    }
+
+    pub fn sample(&self, name: &str) {
+        if self.format == Format::Text {
+            println!("  {}:", name);
+        }
+    }
+
+    pub fn start(&self, entry: &Entry, part: Part) {
+        if self.format == Format::Text {
+            print!("  - part {:?} ... {} ... ", part, entry.alt);
+        }
+    }
+
+    /// Accounts for an input being parsed, since records can share the same parsed input.
+    pub fn parsed(&mut self, duration: Duration) {
+        self.day.1.parse += duration;
+        self.run.1.parse += duration;
+    }
+
+    pub fn record(&mut self, record: &Record) -> anyhow::Result<()> {
+        let solve = record.solve_time.unwrap_or_default();
+        for (count, total) in [&mut self.day, &mut self.run] {
+            *count += 1;
+            total.solve += solve;
+        }
+
+        match self.format {
+            Format::Text => println!("{}", text(record, self.verbose)),
+            Format::Json => {
+                let mut stdout = io::stdout();
+                serde_json::to_writer(&mut stdout, record)?;
+                writeln!(stdout)?;
+            }
+            Format::Csv => {
+                self.csv.serialize(record)?;
+                self.csv.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports the total timings of the day, if anything ran.
+    pub fn end_day(&mut self) {
+        let (count, timings) = std::mem::take(&mut self.day);
+        if count > 0 {
+            self.note(format!("  - total ... {}", total(timings)));
+        }
+    }
+
+    pub fn end_run(&mut self) {
+        let (count, timings) = std::mem::take(&mut self.run);
+        if count > 0 {
+            self.note(format!("Total ... {}", total(timings)));
+        }
+    }
+}
+
+fn total(timings: Timings) -> String {
+    format!(
+        "{:?} (parse {:?}, solve {:?})",
+        timings.total(),
+        timings.parse,
+        timings.solve
+    )
+}
+
+fn text(record: &Record, verbose: bool) -> String {
+    let qualifier = match (record.verdict, &record.expected) {
+        (Verdict::Failed, _) => {
+            return format!(
+                "({}failed{}: {})",
+                color::Fg(color::Red),
+                style::Reset,
+                record.error.as_deref().unwrap_or_default()
+            );
+        }
+        (Verdict::Timeout, _) => {
+            return format!(
+                "({}timeout{}: {})",
+                color::Fg(color::Magenta),
+                style::Reset,
+                record.error.as_deref().unwrap_or_default()
+            );
+        }
+        (Verdict::Absent, _) => return "(no such part)".to_string(),
+        (Verdict::Correct, _) => format!("({}correct{})", color::Fg(color::Green), style::Reset),
+        (Verdict::Mismatch, _) => format!("({}mismatch{})", color::Fg(color::Yellow), style::Reset),
+        (Verdict::Incorrect, Some(expected)) => format!(
+            "({}incorrect{}, expected {})",
+            color::Fg(color::Red),
+            style::Reset,
+            expected
+        ),
+        _ => "(???)".to_string(),
+    };
+    let mut text = format!(
+        "{:?} (parse {:?}) {} {}",
+        record.solve_time.unwrap_or_default(),
+        record.parse_time.unwrap_or_default(),
+        answer_text(record),
+        qualifier
+    );
+    if let Some((parse, solve)) = record.usage() {
+        text.push_str(&format!("\n      memory: {} (parse {})", solve, parse));
+    }
+    if verbose {
+        text.push_str(&metrics(&record.metrics));
+    } else if !record.metrics.is_empty() {
+        text.push_str(&format!("\n      metrics: {}", record.metrics));
+    }
+    text
+}
+
+// each counter and histogram on a line of its own, with the ranges the values of histograms fell in
+fn metrics(metrics: &Metrics) -> String {
+    let mut text = String::new();
+    for (name, total) in &metrics.counters {
+        text.push_str(&format!("\n      {}: {}", name, total));
+    }
+    for (name, histogram) in &metrics.histograms {
+        text.push_str(&format!("\n      {}: {}", name, histogram));
+        for (range, count) in histogram.buckets() {
+            text.push_str(&format!("\n        {:?}: {}", range, count));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(expected: Option<&str>) -> Record {
+        Record {
+            year: 2021,
+            day: 7,
+            part: Part::Two,
+            alt: "Main",
+            sample: None,
+            answer: None,
+            expected: expected.map(|s| s.to_string()),
+            verdict: Verdict::Unknown,
+            parse_time: None,
+            solve_time: None,
+            error: None,
+            parse_allocs: None,
+            parse_alloc_bytes: None,
+            parse_peak_bytes: None,
+            solve_allocs: None,
+            solve_alloc_bytes: None,
+            solve_peak_bytes: None,
+            metrics: Metrics::default(),
+        }
+    }
+
+    fn timings() -> Timings {
+        Timings {
+            parse: Duration::from_micros(3),
+            solve: Duration::from_micros(5),
+        }
+    }
+
+    #[test]
+    fn test_verdict() {
+        let mut r = record(Some("12"));
+        r.solved(Answer::Number(12), timings());
+        assert_eq!(r.verdict, Verdict::Correct);
+
+        let mut r = record(Some("12"));
+        r.solved(Answer::Number(13), timings());
+        assert_eq!(r.verdict, Verdict::Incorrect);
+
+        // answers are compared normalized
+        let mut r = record(Some("#.\n.#\n"));
+        r.solved(Answer::from("\n#.\n.#"), timings());
+        assert_eq!(r.verdict, Verdict::Correct);
+
+        let mut r = record(Some("12"));
+        r.solved(Answer::None, timings());
+        assert_eq!((r.verdict, r.answer), (Verdict::Absent, None));
+
+        let mut r = record(None);
+        r.solved(Answer::Number(13), timings());
+        assert_eq!(r.verdict, Verdict::Unknown);
+
+        let mut r = record(Some("12"));
+        r.failed("oops");
+        assert_eq!(r.verdict, Verdict::Failed);
+
+        let mut r = record(Some("12"));
+        r.given_up(GivenUp::TimedOut(Duration::from_secs(2)));
+        assert_eq!(r.verdict, Verdict::Timeout);
+        assert_eq!(r.error.as_deref(), Some("timed out after 2s"));
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let mut records = [Part::One, Part::Two, Part::One, Part::Two].map(|part| Record {
+            part,
+            ..record(None)
+        });
+        records[2].alt = "Alt";
+        records[3].alt = "Alt";
+        records[0].solved(Answer::Number(12), timings());
+        // the same number, once as text
+        records[1].solved(Answer::from("03"), timings());
+        records[2].solved(Answer::Number(13), timings());
+        records[3].solved(Answer::Number(3), timings());
+
+        assert_eq!(cross_check(&mut records), vec!["part One: Main 12, Alt 13"]);
+        assert_eq!(records[0].verdict, Verdict::Mismatch);
+        assert_eq!(records[1].verdict, Verdict::Unknown);
+        assert_eq!(records[2].verdict, Verdict::Mismatch);
+        assert_eq!(records[3].verdict, Verdict::Unknown);
+
+        // a known answer is more telling than a mismatch
+        records[0].expected = Some("13".to_string());
+        records[0].solved(Answer::Number(12), timings());
+        records[2].verdict = Verdict::Unknown;
+        assert_eq!(cross_check(&mut records).len(), 1);
+        assert_eq!(records[0].verdict, Verdict::Incorrect);
+        assert_eq!(records[2].verdict, Verdict::Mismatch);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut r = record(Some("12"));
+        r.solved(Answer::Number(12), timings());
+        assert_eq!(
+            serde_json::to_string(&r).unwrap(),
+            r#"{"year":2021,"day":7,"part":2,"alt":"Main","sample":null,"answer":"12","expected":"12","verdict":"correct","parse_ns":3000,"solve_ns":5000,"error":null,"parse_allocs":null,"parse_alloc_bytes":null,"parse_peak_bytes":null,"solve_allocs":null,"solve_alloc_bytes":null,"solve_peak_bytes":null,"metrics":null}"#
+        );
+
+        let mut r = record(None);
+        let usage = |allocs| Usage {
+            allocs,
+            bytes: 100,
+            peak: 10,
+        };
+        r.used(Some(usage(1)), Some(usage(2)));
+        let mut csv = csv::Writer::from_writer(vec![]);
+        csv.serialize(record(None)).unwrap();
+        csv.serialize(r).unwrap();
+        assert_eq!(
+            String::from_utf8(csv.into_inner().unwrap()).unwrap(),
+            "year,day,part,alt,sample,answer,expected,verdict,parse_ns,solve_ns,error,\
+            parse_allocs,parse_alloc_bytes,parse_peak_bytes,solve_allocs,solve_alloc_bytes,solve_peak_bytes,metrics\n\
+            2021,7,2,Main,,,,unknown,,,,,,,,,,\n\
+            2021,7,2,Main,,,,unknown,,,,1,100,10,2,100,10,\n"
+        );
+    }
+}
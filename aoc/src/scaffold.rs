@@ -0,0 +1,328 @@
+use anyhow::{bail, Context};
+use aoc_lib::puzzle::{self, Candidate};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// What `new_day` did.
+pub struct Scaffold {
+    pub module: PathBuf,
+    pub lib: PathBuf,
+    /// The number of examples found in the cached description, if there was one
+    pub examples: Option<usize>,
+}
+
+/// Generates the module of a day in the crate of its year, e.g. aoc_2021/src/day7.rs, and declares it in `days!`.
+pub fn new_day(root: &Path, year: u16, day: u8) -> anyhow::Result<Scaffold> {
+    if !(1..=25).contains(&day) {
+        bail!("there's no day {}, days go from 1 to 25", day);
+    }
+    let src = root.join(format!("aoc_{}", year)).join("src");
+    let lib = src.join("lib.rs");
+    let module = src.join(format!("day{}.rs", day));
+    if !lib.exists() {
+        bail!(
+            "no crate for {} in {}, run this from the workspace root",
+            year,
+            root.display()
+        );
+    }
+    if module.exists() {
+        bail!("{} already exists", module.display());
+    }
+
+    let candidates =
+        cached_description(root, year, day).map(|description| puzzle::candidates(&description));
+    let declared = declare(&fs::read_to_string(&lib)?, day)
+        .with_context(|| format!("cannot declare day{} in {}", day, lib.display()))?;
+    fs::write(
+        &module,
+        template(day, candidates.as_deref().unwrap_or_default()),
+    )?;
+    fs::write(&lib, declared)?;
+    // the layout of multi-line examples is left to rustfmt, if it's installed
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(&module)
+        .status();
+    Ok(Scaffold {
+        module,
+        lib,
+        examples: candidates.map(|candidates| candidates.len()),
+    })
+}
+
+/// The description of a day as cached by aocf, in markdown, e.g. from .aocf/cache/aoc2021_07.json
+pub fn cached_description(root: &Path, year: u16, day: u8) -> Option<String> {
+    let path = root
+        .join(".aocf")
+        .join("cache")
+        .join(format!("aoc{}_{:02}.json", year, day));
+    let cache: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let brief = cache.get("brief")?;
+    // the page of part two describes both parts
+    ["second", "first"]
+        .iter()
+        .find_map(|level| brief.get(*level)?.as_str())
+        .map(str::to_string)
+}
+
+// adds a day to the `days!` list of a year crate, sorted and wrapped the way rustfmt leaves it
+fn declare(lib: &str, day: u8) -> anyhow::Result<String> {
+    let start = lib.find("days!(").context("no days! invocation")? + "days!(".len();
+    let end = start
+        + lib[start..]
+            .find(");")
+            .context("unterminated days! invocation")?;
+    let mut days = lib[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .map(|module| {
+            module
+                .strip_prefix("day")
+                .and_then(|n| n.parse::<u8>().ok())
+                .with_context(|| format!("unexpected module {} in days!", module))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if days.contains(&day) {
+        bail!("day{} is already declared", day);
+    }
+    days.push(day);
+    days.sort_unstable();
+
+    let mut lines = vec![];
+    let mut line = String::new();
+    for day in days {
+        let module = format!("day{},", day);
+        if !line.is_empty() && 4 + line.len() + 1 + module.len() > 100 {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&module);
+    }
+    lines.push(line);
+    let list = lines
+        .iter()
+        .map(|line| format!("    {}\n", line))
+        .collect::<String>();
+    Ok(format!("{}\n{}{}", &lib[..start], list, &lib[end..]))
+}
+
+// a string literal, raw if it needs escaping
+fn literal(s: &str) -> String {
+    if !s.contains('"') && !s.contains('\\') {
+        return format!("\"{}\"", s);
+    }
+    let mut hashes = "#".to_string();
+    while s.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, s, hashes)
+}
+
+// an example in `sample!`, on its own line unless its content spans several
+fn example(name: &str, candidate: &Candidate) -> String {
+    let answers = [(1, &candidate.part1), (2, &candidate.part2)]
+        .iter()
+        .filter_map(|(part, answer)| Some(format!(".part{}({})", part, literal(answer.as_ref()?))))
+        .collect::<String>();
+    if candidate.content.contains('\n') {
+        format!(
+            "    Example::new(\n        \"{}\",\n        {},\n    ){},\n",
+            name,
+            literal(&candidate.content),
+            answers
+        )
+    } else {
+        format!(
+            "    Example::new(\"{}\", {}){},\n",
+            name,
+            literal(&candidate.content),
+            answers
+        )
+    }
+}
+
+/// A `sample!` invocation declaring the candidates of a day.
+pub fn sample_macro(day: u8, candidates: &[Candidate]) -> String {
+    let examples = match candidates {
+        [] => return format!("sample!(Day{});", day),
+        [candidate] => example("sample", candidate),
+        candidates => candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, candidate)| example(&format!("example {}", idx + 1), candidate))
+            .collect(),
+    };
+    format!("sample!(\n    Day{},\n{});", day, examples)
+}
+
+fn template(day: u8, candidates: &[Candidate]) -> String {
+    TEMPLATE
+        .replace("{samples}", &sample_macro(day, candidates))
+        .replace("{n}", &day.to_string())
+}
+
+const TEMPLATE: &str = r#"use aoc_lib::*;
+
+day!(Day{n}, {n});
+register!(Day{n});
+
+{samples}
+
+impl Solver for Day{n} {
+    type Output = usize;
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(_input: &Self::Input) -> Self::Output {
+        todo!()
+    }
+
+    fn part2(_input: &Self::Input) -> Self::Output {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_lib::Sample;
+
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        for sample in Day{n}::SAMPLES {
+            let input = Day{n}::parse(sample.content).unwrap();
+            if let Some(answer) = sample.part1 {
+                assert_eq!(Day{n}::part1(&input).to_string(), answer, "{}", sample.name);
+            }
+            if let Some(answer) = sample.part2 {
+                assert_eq!(Day{n}::part2(&input).to_string(), answer, "{}", sample.name);
+            }
+        }
+    }
+}
+"#;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_declare() {
+        let lib = "pub(crate) mod grid;\n\ndays!(\n    day1, day2,\n);\n\n#[cfg(test)]\n";
+        assert_eq!(
+            declare(lib, 3).unwrap(),
+            "pub(crate) mod grid;\n\ndays!(\n    day1, day2, day3,\n);\n\n#[cfg(test)]\n"
+        );
+        assert!(declare(lib, 2).is_err());
+
+        let all = declare("days!(day2);", 1).unwrap();
+        let all = (3..=25).fold(all, |lib, day| declare(&lib, day).unwrap());
+        assert_eq!(
+            all,
+            "days!(\n    \
+            day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14, day15,\n    \
+            day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,\n);"
+        );
+    }
+
+    #[test]
+    fn test_sample_macro() {
+        let candidate = |content: &str, part1: Option<&str>| Candidate {
+            content: content.to_string(),
+            part1: part1.map(str::to_string),
+            part2: None,
+        };
+        assert_eq!(sample_macro(3, &[]), "sample!(Day3);");
+        assert_eq!(
+            sample_macro(3, &[candidate("1\n2", Some("3"))]),
+            "sample!(\n    Day3,\n    Example::new(\n        \"sample\",\n        \"1\n2\",\n    ).part1(\"3\"),\n);"
+        );
+        assert_eq!(
+            sample_macro(3, &[candidate("1", None), candidate("2", Some("4"))]),
+            "sample!(\n    Day3,\n    Example::new(\"example 1\", \"1\"),\n    \
+            Example::new(\"example 2\", \"2\").part1(\"4\"),\n);"
+        );
+    }
+
+    // a tree with the cache aocf leaves for day 1 of 2021, once both parts are solved
+    fn cached_tree() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let cache = root.path().join(".aocf").join("cache");
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(
+            cache.join("aoc2021_01.json"),
+            include_str!("../tests/fixtures/aoc2021_01.json"),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_cached_description() {
+        let root = cached_tree();
+        assert!(cached_description(root.path(), 2021, 2).is_none());
+        let description = cached_description(root.path(), 2021, 1).unwrap();
+        let candidates = puzzle::candidates(&description);
+        let summary = candidates
+            .iter()
+            .map(|c| {
+                let first = c.content.lines().next().unwrap_or_default();
+                (first, c.part1.as_deref(), c.part2.as_deref())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("199", Some("7"), None),
+                ("199 (N/A - no previous measurement)", None, None),
+                ("199  A      ", None, Some("5")),
+                ("A: 607 (N/A - no previous sum)", None, None),
+            ]
+        );
+        assert_eq!(
+            candidates[0].content,
+            "199\n200\n208\n210\n200\n207\n240\n269\n260\n263"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = cached_tree();
+        let src = root.path().join("aoc_2021").join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "days!(day2);\n").unwrap();
+
+        let scaffold = new_day(root.path(), 2021, 1).unwrap();
+        assert_eq!(scaffold.examples, Some(4));
+        assert_eq!(
+            fs::read_to_string(&scaffold.lib).unwrap(),
+            "days!(\n    day1, day2,\n);\n"
+        );
+        // however rustfmt laid it out, if it's installed
+        let module = fs::read_to_string(&scaffold.module).unwrap();
+        assert!(
+            module.contains("\"199\n200\n208\n210\n200\n207\n240\n269\n260\n263\""),
+            "{}",
+            module
+        );
+        assert!(module.contains(".part1(\"7\")"), "{}", module);
+        assert!(module.contains(".part2(\"5\")"), "{}", module);
+        assert!(new_day(root.path(), 2021, 1).is_err());
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(literal("1\n2"), "\"1\n2\"");
+        assert_eq!(literal("say \"hi\""), "r#\"say \"hi\"\"#");
+        assert_eq!(literal("\"# \\"), "r##\"\"# \\\"##");
+    }
+}
@@ -0,0 +1,170 @@
+//! Solves inputs posted over HTTP, for tools that can't link the solvers, e.g.:
+//! `curl -d '{"year": 2021, "day": 7, "part": 1, "input": "16,1,2,0,4,2,7,1,2,14"}' localhost:8080`
+//!
+//! The response is the record of the part, as output by --format json.
+use crate::output::Record;
+use crate::timeout::Timeout;
+use aoc_lib::{Part, Registry};
+use serde::Deserialize;
+use std::io::Read;
+use tiny_http::{Header, Method, Response, Server};
+
+/// The most bytes a request can have, puzzle inputs are well below.
+const MAX_BODY: usize = 1 << 20;
+
+/// What to solve, the alternate defaults to the first one registered and the verdict needs an expected answer.
+#[derive(Debug, Deserialize)]
+struct Request {
+    year: u16,
+    day: u8,
+    part: u8,
+    alt: Option<String>,
+    input: String,
+    expected: Option<String>,
+}
+
+/// Answers requests in the pool until the process is stopped, solving each the way parts are with `--timeout`.
+pub fn serve(registry: &Registry, port: u16, timeout: &Timeout) -> anyhow::Result<()> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| anyhow::anyhow!(e))?;
+    println!("Listening on http://{}", server.server_addr());
+    // in place, waiting for requests would take a thread of the pool otherwise
+    rayon::in_place_scope(|scope| {
+        for mut request in server.incoming_requests() {
+            scope.spawn(move |_| {
+                let (status, body) = if *request.method() != Method::Post {
+                    (405, error("only POST is supported"))
+                } else {
+                    match read_body(request.as_reader()) {
+                        Ok(body) => respond(registry, timeout, &body),
+                        Err(response) => response,
+                    }
+                };
+                let json = Header::from_bytes("Content-Type", "application/json").unwrap();
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(json);
+                if let Err(e) = request.respond(response) {
+                    eprintln!("cannot respond: {}", e);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+// the body of a request, or the response to it when it's too large or can't be read
+fn read_body(reader: impl Read) -> Result<String, (u16, String)> {
+    let mut body = String::new();
+    match reader.take(MAX_BODY as u64 + 1).read_to_string(&mut body) {
+        Ok(len) if len > MAX_BODY => Err((413, error(format!("more than {} bytes", MAX_BODY)))),
+        Ok(_) => Ok(body),
+        Err(e) => Err((400, error(e))),
+    }
+}
+
+// the status and body of the response to a request
+fn respond(registry: &Registry, timeout: &Timeout, body: &str) -> (u16, String) {
+    let request = match serde_json::from_str::<Request>(body) {
+        Ok(request) => request,
+        Err(e) => return (400, error(format!("invalid request: {}", e))),
+    };
+    let part = match request.part {
+        1 => Part::One,
+        2 => Part::Two,
+        part => return (400, error(format!("invalid part {}", part))),
+    };
+    let mut entries = registry.day(request.year, request.day);
+    let entry = match &request.alt {
+        Some(alt) => entries.find(|entry| entry.alt.eq_ignore_ascii_case(alt)),
+        None => entries.next(),
+    };
+    let entry = match entry {
+        Some(entry) => *entry,
+        None => return (404, error("no such day or alternate")),
+    };
+
+    let mut record = Record::new(&entry, part, request.expected);
+    let input = request.input;
+    // parsing isn't guarded against panics like solving is
+    let solved = timeout.run(move || {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| entry.solve(&input, part)))
+    });
+    match solved {
+        Ok(Ok(Ok((answer, timings)))) => record.solved(answer, timings),
+        Ok(Ok(Err(e))) => record.failed(e),
+        Ok(Err(_)) => record.failed("parsing panicked"),
+        Err(given_up) => record.given_up(given_up),
+    }
+    match serde_json::to_string(&record) {
+        Ok(json) => (200, json),
+        Err(e) => (500, error(e)),
+    }
+}
+
+fn error(message: impl ToString) -> String {
+    serde_json::json!({ "error": message.to_string() }).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_respond() {
+        let registry = aoc_2021::registry();
+        let (status, body) = respond(
+            &registry,
+            &Timeout::default(),
+            r#"{"year": 2021, "day": 7, "part": 2, "input": "16,1,2,0,4,2,7,1,2,14", "expected": "168"}"#,
+        );
+        assert_eq!(status, 200);
+        let record = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(record["answer"], "168");
+        assert_eq!(record["verdict"], "correct");
+        assert_eq!(record["alt"], "Main");
+        assert!(record["solve_ns"].is_u64());
+
+        let (status, body) = respond(
+            &registry,
+            &Timeout::default(),
+            r#"{"year": 2021, "day": 7, "part": 1, "alt": "nope", "input": ""}"#,
+        );
+        assert_eq!(
+            (status, body.as_str()),
+            (404, r#"{"error":"no such day or alternate"}"#)
+        );
+        assert_eq!(respond(&registry, &Timeout::default(), "{}").0, 400);
+
+        let (status, body) = respond(
+            &registry,
+            &Timeout::default(),
+            r#"{"year": 2021, "day": 7, "part": 1, "input": "x"}"#,
+        );
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""verdict":"failed""#), "{}", body);
+    }
+
+    #[test]
+    fn test_respond_timeout() {
+        let registry = crate::timeout::test::stuck();
+        let timeout = Timeout::new(Some(Duration::from_millis(20)));
+        let (status, body) = respond(
+            &registry,
+            &timeout,
+            r#"{"year": 2021, "day": 1, "part": 1, "input": ""}"#,
+        );
+        assert_eq!(status, 200);
+        let record = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(record["verdict"], "timeout");
+        assert_eq!(record["error"], "timed out after 20ms");
+    }
+
+    #[test]
+    fn test_read_body() {
+        assert_eq!(read_body("{}".as_bytes()), Ok("{}".to_string()));
+        let (status, _) = read_body(std::io::repeat(b' ')).unwrap_err();
+        assert_eq!(status, 413);
+        assert!(read_body(" ".repeat(MAX_BODY).as_bytes()).is_ok());
+    }
+}
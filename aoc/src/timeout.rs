@@ -0,0 +1,154 @@
+//! Giving up on parsing or solving after a while, see `--timeout`.
+//!
+//! Threads can't be stopped: a task given up on keeps running in the background until it's done or the process exits,
+//! skewing every timing taken meanwhile. So no other task is started while one is still running.
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// Why a task has no result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GivenUp {
+    TimedOut(Duration),
+    /// Not started, a task that timed out was still running
+    Skipped,
+}
+
+impl Display for GivenUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GivenUp::TimedOut(after) => write!(f, "timed out after {:?}", after),
+            GivenUp::Skipped => write!(f, "not started, a task that timed out is still running"),
+        }
+    }
+}
+
+/// The timeout of some tasks, which keeps track of those given up on that are still running.
+#[derive(Debug, Clone, Default)]
+pub struct Timeout {
+    after: Option<Duration>,
+    running: Arc<AtomicUsize>,
+}
+
+impl Timeout {
+    pub fn new(after: Option<Duration>) -> Self {
+        Self {
+            after,
+            running: Arc::default(),
+        }
+    }
+
+    /// Runs a task, on a thread of its own when there's a timeout, and passes its panics on.
+    pub fn run<T: Send + 'static>(
+        &self,
+        task: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, GivenUp> {
+        let after = match self.after {
+            Some(after) => after,
+            None => return Ok(task()),
+        };
+        if self.running.load(Ordering::SeqCst) > 0 {
+            return Err(GivenUp::Skipped);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        // whether the task was given up on, it's either sent or counted as still running when done
+        let given_up = Arc::new(Mutex::new(false));
+        let (running, task_given_up) = (self.running.clone(), given_up.clone());
+        std::thread::spawn(move || {
+            let done = std::panic::catch_unwind(std::panic::AssertUnwindSafe(task));
+            let given_up = task_given_up.lock().unwrap();
+            if *given_up {
+                running.fetch_sub(1, Ordering::SeqCst);
+            } else {
+                let _ = tx.send(done);
+            }
+        });
+
+        let done = match rx.recv_timeout(after) {
+            Ok(done) => done,
+            Err(_) => {
+                let mut given_up = given_up.lock().unwrap();
+                // it may have been done since
+                match rx.try_recv() {
+                    Ok(done) => done,
+                    Err(_) => {
+                        *given_up = true;
+                        self.running.fetch_add(1, Ordering::SeqCst);
+                        return Err(GivenUp::TimedOut(after));
+                    }
+                }
+            }
+        };
+        match done {
+            Ok(done) => Ok(done),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use aoc_lib::{Day, Example, Main, ParseError, Registry, Sample, Solver};
+
+    /// A day whose part one never finishes.
+    struct Stuck;
+    impl Day for Stuck {
+        const YEAR: u16 = 2021;
+        const DAY: u8 = 1;
+    }
+    impl Sample for Stuck {
+        const SAMPLES: &'static [Example] = &[];
+    }
+    impl Solver for Stuck {
+        type Output = u32;
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> Self::Output {
+            loop {
+                std::thread::park();
+            }
+        }
+
+        fn part2(_input: &Self::Input) -> Self::Output {
+            2
+        }
+    }
+
+    pub(crate) fn stuck() -> Registry {
+        let mut registry = Registry::new();
+        registry.register::<Stuck, Main>();
+        registry
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(Timeout::new(None).run(|| 1), Ok(1));
+
+        // long enough for the other tasks to be done in time
+        let timeout = Timeout::new(Some(Duration::from_secs(1)));
+        assert_eq!(timeout.run(|| 2), Ok(2));
+        let (release, wait) = mpsc::channel::<()>();
+        assert_eq!(
+            timeout.run(move || wait.recv().is_ok()),
+            Err(GivenUp::TimedOut(Duration::from_secs(1)))
+        );
+        assert_eq!(timeout.run(|| 3), Err(GivenUp::Skipped));
+
+        // tasks start again once the one given up on is done
+        release.send(()).unwrap();
+        while timeout.running.load(Ordering::SeqCst) > 0 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(timeout.run(|| 4), Ok(4));
+
+        let panicked = std::panic::catch_unwind(|| timeout.run(|| panic!("oops")));
+        assert!(panicked.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Context;
+use aoc_lib::trace::{Event, Filter, Value};
+use aoc_lib::{Entry, Part};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// An event of a parse or part, one per line of the trace file.
+#[derive(Debug, Serialize)]
+struct Traced<'a> {
+    year: u16,
+    day: u8,
+    /// None for the events of parsing
+    part: Option<u8>,
+    alt: &'a str,
+    sample: Option<&'a str>,
+    kind: &'a str,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Writes the events solvers emit for some days as JSON lines, e.g.:
+/// `{"year":2021,"day":18,"part":1,"alt":"Main","sample":null,"kind":"explode","fields":{"number":"[[1,2],3]"}}`
+pub struct Tracer {
+    out: BufWriter<File>,
+    days: Vec<u8>,
+    filter: Filter,
+}
+
+impl Tracer {
+    pub fn create(path: &Path, days: Vec<u8>, kinds: Vec<String>) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("cannot create trace {}", path.display()))?;
+        Ok(Self {
+            out: BufWriter::new(file),
+            days,
+            filter: Filter { kinds },
+        })
+    }
+
+    /// The events to record for a day, none if it isn't traced.
+    pub fn filter(&self, day: u8) -> Option<&Filter> {
+        if self.days.contains(&day) {
+            Some(&self.filter)
+        } else {
+            None
+        }
+    }
+
+    pub fn write(
+        &mut self,
+        entry: &Entry,
+        part: Option<Part>,
+        sample: Option<&str>,
+        events: &[Event],
+    ) -> anyhow::Result<()> {
+        for event in events {
+            let traced = Traced {
+                year: entry.year,
+                day: entry.day,
+                part: part.map(|part| match part {
+                    Part::One => 1,
+                    Part::Two => 2,
+                }),
+                alt: entry.alt,
+                sample,
+                kind: event.kind,
+                fields: event
+                    .fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), json(value)))
+                    .collect(),
+            };
+            serde_json::to_writer(&mut self.out, &traced)?;
+            writeln!(self.out)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        self.out.flush().context("cannot write trace")
+    }
+}
+
+fn json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Int(n) => (*n).into(),
+        Value::Float(x) => (*x).into(),
+        Value::Bool(b) => (*b).into(),
+        Value::Text(s) => s.as_str().into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_lib::trace;
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        let mut tracer = Tracer::create(&path, vec![18], vec![]).unwrap();
+        assert!(tracer.filter(18).is_some());
+        assert!(tracer.filter(1).is_none());
+
+        let (_, events) = trace::record(tracer.filter(18), || {
+            aoc_lib::trace!("explode", number = "[[1,2],3]", depth = 4);
+        });
+        let entry = aoc_2021::registry().day(2021, 18).next().copied().unwrap();
+        tracer
+            .write(&entry, Some(Part::One), None, &events)
+            .unwrap();
+        tracer.write(&entry, None, Some("sum"), &events).unwrap();
+        tracer.flush().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"year\":2021,\"day\":18,\"part\":1,\"alt\":\"Main\",\"sample\":null,\"kind\":\"explode\",\
+                \"fields\":{\"depth\":4,\"number\":\"[[1,2],3]\"}}\n\
+            {\"year\":2021,\"day\":18,\"part\":null,\"alt\":\"Main\",\"sample\":\"sum\",\"kind\":\"explode\",\
+                \"fields\":{\"depth\":4,\"number\":\"[[1,2],3]\"}}\n"
+        );
+    }
+}
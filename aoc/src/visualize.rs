@@ -0,0 +1,113 @@
+use aoc_lib::Frame;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+use termion::raw::IntoRawMode;
+use termion::{clear, cursor, AsyncReader};
+
+const HELP: &str = "space: pause, n: next frame, +/-: speed, q: quit";
+
+/// Plays frames in the terminal, the speed and pause carry over from one animation to the next.
+pub struct Player {
+    fps: f64,
+    paused: bool,
+    keys: AsyncReader,
+}
+
+impl Player {
+    pub fn new(fps: f64) -> Self {
+        Self {
+            fps: fps.clamp(0.5, 1000.0),
+            paused: false,
+            keys: termion::async_stdin(),
+        }
+    }
+
+    /// Plays frames until there are no more, returns false if the user quit.
+    pub fn play(&mut self, title: &str, frames: impl Iterator<Item = Frame>) -> io::Result<bool> {
+        let mut out = io::stdout().into_raw_mode()?;
+        write!(out, "{}", cursor::Hide)?;
+        let played = self.play_on(&mut out, title, frames);
+        write!(out, "{}", cursor::Show)?;
+        out.flush()?;
+        played
+    }
+
+    fn play_on(
+        &mut self,
+        out: &mut impl Write,
+        title: &str,
+        frames: impl Iterator<Item = Frame>,
+    ) -> io::Result<bool> {
+        for (idx, frame) in frames.enumerate() {
+            let heading = format!("{}, frame {}: {}", title, idx + 1, frame.caption);
+            self.draw(out, &heading, &frame.content)?;
+            let shown = Instant::now();
+            loop {
+                let key = self.key();
+                match key {
+                    // ctrl-c doesn't interrupt in raw mode
+                    Some(b'q') | Some(3) => return Ok(false),
+                    Some(b' ') => self.paused = !self.paused,
+                    Some(b'n') if self.paused => break,
+                    Some(b'+') => self.fps = (self.fps * 2.0).min(1000.0),
+                    Some(b'-') => self.fps = (self.fps / 2.0).max(0.5),
+                    Some(_) => continue,
+                    None => {}
+                }
+                if key.is_some() {
+                    self.status(out)?;
+                }
+                if !self.paused && shown.elapsed().as_secs_f64() * self.fps >= 1.0 {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        Ok(true)
+    }
+
+    // the next key pressed, if any, without waiting
+    fn key(&mut self) -> Option<u8> {
+        let mut key = [0];
+        match self.keys.read(&mut key) {
+            Ok(1) => Some(key[0]),
+            _ => None,
+        }
+    }
+
+    // frames are clipped to the terminal, the last line is for the status
+    fn draw(&self, out: &mut impl Write, heading: &str, content: &str) -> io::Result<()> {
+        let (cols, rows) = terminal_size();
+        write!(out, "{}{}", clear::All, cursor::Goto(1, 1))?;
+        let heading = heading.chars().take(cols as usize).collect::<String>();
+        write!(out, "{}\r\n", heading)?;
+        for line in content.lines().take(rows.saturating_sub(2) as usize) {
+            let line = line.chars().take(cols as usize).collect::<String>();
+            write!(out, "{}\r\n", line)?;
+        }
+        self.status(out)
+    }
+
+    fn status(&self, out: &mut impl Write) -> io::Result<()> {
+        let (_, rows) = terminal_size();
+        let state = if self.paused { "paused" } else { "playing" };
+        write!(
+            out,
+            "{}{}{} at {} fps - {}",
+            cursor::Goto(1, rows),
+            clear::CurrentLine,
+            state,
+            self.fps,
+            HELP
+        )?;
+        out.flush()
+    }
+}
+
+// some terminals, e.g. pseudo terminals without a size, report nothing useful
+fn terminal_size() -> (u16, u16) {
+    match termion::terminal_size() {
+        Ok((cols, rows)) if cols > 0 && rows > 2 => (cols, rows),
+        _ => (80, 24),
+    }
+}
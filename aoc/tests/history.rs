@@ -0,0 +1,25 @@
+use std::process::Command;
+
+#[test]
+fn test_default_history() {
+    let root = tempfile::tempdir().unwrap();
+    let status = Command::new("git")
+        .arg("init")
+        .current_dir(root.path())
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+    let dir = root.path().join("aoc_2021");
+    std::fs::create_dir(&dir).unwrap();
+
+    // the history is shared by the whole repository wherever it's run from
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["sample", "-y", "2021", "-d", "1"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(root.path().join("history.jsonl").exists());
+    assert!(!dir.join("history.jsonl").exists());
+}
@@ -0,0 +1,58 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn test_stdin_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["-y", "2021", "-d", "7", "-i", "-"])
+        .args(["--expect", "37", "--expect", "170"])
+        .arg("--history")
+        .arg(dir.path().join("history.jsonl"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"16,1,2,0,4,2,7,1,2,14\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let out = String::from_utf8(output.stdout).unwrap();
+    let (part1, part2) = out.split_once("part Two").unwrap();
+    assert!(
+        part1.contains(" 37 ") && !part1.contains("incorrect"),
+        "{}",
+        out
+    );
+    assert!(
+        part2.contains("incorrect") && part2.contains("expected 170"),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn test_input_not_submitted() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "-y", "2021", "-d", "7", "-i", "-"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let err = String::from_utf8(output.stderr).unwrap();
+    assert!(err.contains("not to --input(s)"), "{}", err);
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "-y", "2021", "-d", "7", "--inputs"])
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let err = String::from_utf8(output.stderr).unwrap();
+    assert!(err.contains("not to --input(s)"), "{}", err);
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tiny_http::{Method, Response, Server};
+
+/// A stand-in for the puzzle site, serving canned inputs and answering submissions from a script.
+pub struct Mock {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    inputs: HashMap<(u16, u8), String>,
+    accepted: HashMap<(u16, u8), Vec<String>>,
+    verdicts: VecDeque<String>,
+    submissions: Vec<(u16, u8, u8, String)>,
+}
+
+impl Mock {
+    pub fn start() -> Self {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let state = Arc::new(Mutex::new(State::default()));
+        let shared = state.clone();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let (status, content) =
+                    shared
+                        .lock()
+                        .unwrap()
+                        .handle(request.method(), request.url(), &body);
+                let response = Response::from_string(content).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn input(self, year: u16, day: u8, input: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .inputs
+            .insert((year, day), input.to_string());
+        self
+    }
+
+    /// The response to the next submission, answers are accepted when it says they're right.
+    pub fn verdict(self, response: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .verdicts
+            .push_back(response.to_string());
+        self
+    }
+
+    /// All the (year, day, level, answer) submitted so far.
+    pub fn submissions(&self) -> Vec<(u16, u8, u8, String)> {
+        self.state.lock().unwrap().submissions.clone()
+    }
+}
+
+impl State {
+    fn handle(&mut self, method: &Method, url: &str, body: &str) -> (u16, String) {
+        let path = url.split('/').skip(1).collect::<Vec<_>>();
+        let (year, day) = match path[..] {
+            [year, "day", day, ..] => match (year.parse(), day.parse()) {
+                (Ok(year), Ok(day)) => (year, day),
+                _ => return (404, "not found".to_string()),
+            },
+            _ => return (404, "not found".to_string()),
+        };
+
+        match (method, &path[3..]) {
+            (Method::Get, ["input"]) => match self.inputs.get(&(year, day)) {
+                Some(input) => (200, input.clone()),
+                None => (404, "no input".to_string()),
+            },
+            (Method::Get, []) => {
+                let answers = self.accepted.get(&(year, day)).cloned().unwrap_or_default();
+                let page = answers
+                    .iter()
+                    .map(|answer| format!("<p>Your puzzle answer was <code>{}</code>.</p>", answer))
+                    .collect::<String>();
+                (200, format!("<main>{}</main>", page))
+            }
+            (Method::Post, ["answer"]) => {
+                let form = body
+                    .split('&')
+                    .filter_map(|field| field.split_once('='))
+                    .collect::<HashMap<_, _>>();
+                let level = form.get("level").and_then(|l| l.parse().ok()).unwrap_or(0);
+                let answer = form.get("answer").copied().unwrap_or_default().to_string();
+                self.submissions.push((year, day, level, answer.clone()));
+
+                let verdict = match self.verdicts.pop_front() {
+                    Some(verdict) => verdict,
+                    None => return (500, "no verdict scripted".to_string()),
+                };
+                if verdict.contains("That's the right answer") {
+                    self.accepted.entry((year, day)).or_default().push(answer);
+                }
+                (
+                    200,
+                    format!("<main><article><p>{}</p></article></main>", verdict),
+                )
+            }
+            _ => (404, "not found".to_string()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+mod mock;
+
+use mock::Mock;
+use std::path::Path;
+use std::process::Command;
+
+const INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+// runs day 1 of 2021 in submit mode against the mock, returns stdout
+fn submit(mock: &Mock, ledger: &Path, part: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "-y", "2021", "-d", "1", "-p", part])
+        .args(["--server", mock.url()])
+        .arg("--ledger")
+        .arg(ledger)
+        .arg("--history")
+        .arg(ledger.with_file_name("history.jsonl"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_submit_correct() {
+    let dir = tempfile::tempdir().unwrap();
+    let ledger = dir.path().join("submissions.tsv");
+    let mock = Mock::start()
+        .input(2021, 1, INPUT)
+        .verdict("That's the right answer! You are one gold star closer.");
+
+    let out = submit(&mock, &ledger, "1");
+    assert!(
+        out.contains("Submitting solution 7 for day 1 part One"),
+        "{}",
+        out
+    );
+    assert!(out.contains("That's the right answer!"), "{}", out);
+    assert_eq!(mock.submissions(), vec![(2021, 1, 1, "7".to_string())]);
+
+    // the accepted answer is now known
+    let out = submit(&mock, &ledger, "1");
+    assert!(out.contains("correct"), "{}", out);
+    assert!(out.contains("has already been accepted"), "{}", out);
+    assert_eq!(mock.submissions().len(), 1);
+}
+
+#[test]
+fn test_submit_cooldown() {
+    let dir = tempfile::tempdir().unwrap();
+    let ledger = dir.path().join("submissions.tsv");
+    let mock = Mock::start().input(2021, 1, INPUT).verdict(
+        "That's not the right answer; your answer is too high. Please wait one minute before trying again.",
+    );
+
+    let out = submit(&mock, &ledger, "2");
+    assert!(out.contains("your answer is too high"), "{}", out);
+    assert_eq!(mock.submissions(), vec![(2021, 1, 2, "5".to_string())]);
+    assert!(std::fs::read_to_string(&ledger)
+        .unwrap()
+        .contains("too-high"));
+
+    let out = submit(&mock, &ledger, "2");
+    assert!(out.contains("Not submitting solution 5"), "{}", out);
+    assert!(out.contains("submitted too recently"), "{}", out);
+    assert_eq!(mock.submissions().len(), 1);
+}
+
+#[test]
+fn test_submit_out_of_bounds() {
+    let dir = tempfile::tempdir().unwrap();
+    let ledger = dir.path().join("submissions.tsv");
+    std::fs::write(&ledger, "2021\t1\t2\t0\ttoo-low\t60\t10\n").unwrap();
+    let mock = Mock::start().input(2021, 1, INPUT);
+
+    let out = submit(&mock, &ledger, "2");
+    assert!(out.contains("10 was already too low"), "{}", out);
+    assert!(mock.submissions().is_empty());
+}
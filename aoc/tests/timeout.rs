@@ -0,0 +1,14 @@
+use std::process::Command;
+
+#[test]
+fn test_invalid_timeout() {
+    for timeout in ["-1", "NaN", "inf", "0"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["sample", "-y", "2021", "-d", "1"])
+            .arg(format!("--timeout={}", timeout))
+            .output()
+            .unwrap();
+        let err = String::from_utf8(output.stderr).unwrap();
+        assert!(err.contains("positive number of seconds"), "{}", err);
+    }
+}
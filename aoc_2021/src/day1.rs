@@ -1,9 +1,9 @@
 use itertools::Itertools;
-use std::str::FromStr;
 
 use aoc_lib::*;
 
 day!(Day1, 1);
+register!(Day1);
 
 sample!(
     Day1,
@@ -33,18 +33,15 @@ impl Solver for Day1 {
     type Output = usize;
     type Input = Vec<u32>;
 
-    fn parse(input: &str) -> Self::Input {
-        input
-            .lines()
-            .map(|l| u32::from_str(l.trim()).unwrap())
-            .collect()
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|l| parse_at(input, l.trim())).collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        increases(input.into_iter())
+    fn part1(input: &Self::Input) -> Self::Output {
+        increases(input.iter().copied())
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        increases(input.into_iter().tuple_windows().map(|(a, b, c)| a + b + c))
+    fn part2(input: &Self::Input) -> Self::Output {
+        increases(input.iter().tuple_windows().map(|(a, b, c)| a + b + c))
     }
 }
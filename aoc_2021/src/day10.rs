@@ -2,6 +2,7 @@ use itertools::{Either, Itertools};
 
 use aoc_lib::*;
 day!(Day10, 10);
+register!(Day10);
 
 sample!(
     Day10,
@@ -37,26 +38,27 @@ enum Dir {
 pub struct Bracket(Paren, Dir);
 
 impl Bracket {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '[' => Bracket(Paren::Square, Dir::Open),
-            ']' => Bracket(Paren::Square, Dir::Close),
-            '(' => Bracket(Paren::Round, Dir::Open),
-            ')' => Bracket(Paren::Round, Dir::Close),
-            '{' => Bracket(Paren::Curly, Dir::Open),
-            '}' => Bracket(Paren::Curly, Dir::Close),
-            '<' => Bracket(Paren::Pointy, Dir::Open),
-            '>' => Bracket(Paren::Pointy, Dir::Close),
-            _ => panic!("invalid bracket {}", c),
+            '[' => Some(Bracket(Paren::Square, Dir::Open)),
+            ']' => Some(Bracket(Paren::Square, Dir::Close)),
+            '(' => Some(Bracket(Paren::Round, Dir::Open)),
+            ')' => Some(Bracket(Paren::Round, Dir::Close)),
+            '{' => Some(Bracket(Paren::Curly, Dir::Open)),
+            '}' => Some(Bracket(Paren::Curly, Dir::Close)),
+            '<' => Some(Bracket(Paren::Pointy, Dir::Open)),
+            '>' => Some(Bracket(Paren::Pointy, Dir::Close)),
+            _ => None,
         }
     }
 }
 
-fn solve(input: Vec<Bracket>) -> Either<Paren, Vec<Paren>> {
+fn solve(input: &[Bracket]) -> Either<Paren, Vec<Paren>> {
     let mut stack = Vec::new();
 
     let invalid = input
-        .into_iter()
+        .iter()
+        .copied()
         .flat_map(|b| match b {
             Bracket(p, Dir::Open) => {
                 stack.push(p);
@@ -84,16 +86,24 @@ impl Solver for Day10 {
 
     type Input = Vec<Vec<Bracket>>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
-            .map(|s| s.chars().map(Bracket::from_char).collect())
+            .map(|s| {
+                s.char_indices()
+                    .map(|(idx, c)| {
+                        Bracket::from_char(c).ok_or_else(|| {
+                            ParseError::at(input, &s[idx..idx + c.len_utf8()], "invalid bracket")
+                        })
+                    })
+                    .collect()
+            })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         input
-            .into_iter()
+            .iter()
             .flat_map(|l| solve(l).left())
             .map(|c| match c {
                 Paren::Round => 3,
@@ -104,9 +114,9 @@ impl Solver for Day10 {
             .sum::<u32>()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         let scores = input
-            .into_iter()
+            .iter()
             .flat_map(|l| solve(l).right())
             .map(|l| {
                 l.into_iter()
@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day11, 11);
+register!(Day11);
 
 sample!(
     Day11,
@@ -42,6 +43,7 @@ fn step(grid: &mut Grid) -> HashSet<Pt> {
         }
         for pt in to_flash.drain() {
             if flashed.insert(pt) {
+                trace!("flash", x = pt.x, y = pt.y);
                 grid[pt] = 0;
                 let (w, h) = (grid.width() as u8, grid.height() as u8);
                 let new = pt
@@ -61,16 +63,22 @@ impl Solver for Day11 {
 
     type Input = Grid;
 
-    fn parse(input: &str) -> Self::Input {
-        Grid::from_iter(
-            10,
-            input
-                .lines()
-                .flat_map(|l| l.chars().map(|c| c.to_string().parse::<u8>().unwrap())),
-        )
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let energies = input
+            .lines()
+            .flat_map(|l| {
+                l.char_indices().map(move |(idx, c)| {
+                    c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                        ParseError::at(input, &l[idx..idx + c.len_utf8()], "not a digit")
+                    })
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Grid::from_iter(10, energies))
     }
 
-    fn part1(mut input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
+        let mut input = input.clone();
         let mut count = 0;
         for _ in 0..100 {
             count += step(&mut input).len();
@@ -78,7 +86,8 @@ impl Solver for Day11 {
         count
     }
 
-    fn part2(mut input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
+        let mut input = input.clone();
         let mut s = 0;
         loop {
             s += 1;
@@ -88,6 +97,27 @@ impl Solver for Day11 {
         }
         s
     }
+
+    fn visualize(input: &Self::Input, part: Part) -> Option<Frames<'_>> {
+        let mut grid = input.clone();
+        let mut synchronized = false;
+        let steps = (1..).map_while(move |s| {
+            if synchronized || (part == Part::One && s > 100) {
+                return None;
+            }
+            let flashed = step(&mut grid).len();
+            synchronized = flashed == grid.width() * grid.height();
+            let content = grid.render(|_, energy| match energy {
+                0 => '*',
+                e => (b'0' + e) as char,
+            });
+            Some(Frame::new(
+                format!("step {}: {} flashes", s, flashed),
+                content,
+            ))
+        });
+        Some(Box::new(steps))
+    }
 }
 
 #[cfg(test)]
@@ -129,14 +159,17 @@ mod test {
 0021119000",
         ];
 
-        let mut grid = Day11::parse(Day11::CONTENT);
+        let mut grid = Day11::parse(Day11::SAMPLES[0].content).unwrap();
 
-        expected.into_iter().map(Day11::parse).for_each(|expected| {
-            step(&mut grid);
-            assert_eq!(
-                grid.rows_iter().flatten().collect_vec(),
-                expected.rows_iter().flatten().collect_vec()
-            );
-        });
+        expected
+            .into_iter()
+            .map(|e| Day11::parse(e).unwrap())
+            .for_each(|expected| {
+                step(&mut grid);
+                assert_eq!(
+                    grid.rows_iter().flatten().collect_vec(),
+                    expected.rows_iter().flatten().collect_vec()
+                );
+            });
     }
 }
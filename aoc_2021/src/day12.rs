@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use aoc_lib::*;
 day!(Day12, 12);
+register!(Day12);
 
 sample!(
     Day12,
@@ -79,11 +80,11 @@ fn dfs(path: &Path, candidate: &Cave, has_revisited: bool, edges: &Edges) -> usi
     }
 }
 
-fn solve(paths: Vec<Edge>, allow_revisits: bool) -> usize {
+fn solve(paths: &[Edge], allow_revisits: bool) -> usize {
     let mut edges: HashMap<Cave, Vec<Cave>> = HashMap::new();
 
-    paths.into_iter().for_each(|p| {
-        let (from, to) = (p.0, p.1);
+    paths.iter().for_each(|p| {
+        let (from, to) = (p.0.clone(), p.1.clone());
         if to != Cave::Start {
             edges.entry(from.clone()).or_default().push(to.clone());
         }
@@ -100,24 +101,22 @@ impl Solver for Day12 {
 
     type Input = Vec<Edge>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
             .filter(|l| !l.is_empty())
             .map(|path| {
-                let (a, b) = path
-                    .split_once('-')
-                    .unwrap_or_else(|| panic!("unexpected line {}", path));
-                Edge(Cave::from_str(a), Cave::from_str(b))
+                let (a, b) = split_once_at(input, path, "-")?;
+                Ok(Edge(Cave::from_str(a), Cave::from_str(b)))
             })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         solve(input, false)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         solve(input, true)
     }
 }
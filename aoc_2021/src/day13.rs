@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day13, 13);
+register!(Day13);
 
 sample!(
     Day13,
@@ -42,7 +43,8 @@ enum Fold {
 pub struct Paper(HashSet<Pt>, Vec<Fold>);
 
 fn fold_paper(dots: HashSet<Pt>, along: Fold) -> HashSet<Pt> {
-    dots.into_iter()
+    let folded: HashSet<Pt> = dots
+        .into_iter()
         .map(|mut pt| match along {
             Fold::Y(y) => {
                 if pt.y > y {
@@ -59,7 +61,17 @@ fn fold_paper(dots: HashSet<Pt>, along: Fold) -> HashSet<Pt> {
                 pt
             }
         })
-        .collect()
+        .collect();
+    let (axis, at) = match along {
+        Fold::X(x) => ('x', x),
+        Fold::Y(y) => ('y', y),
+    };
+    trace!("fold", axis = axis, at = at, dots = folded.len());
+    folded
+}
+
+fn render(dots: &HashSet<Pt>) -> String {
+    crate::grid::Grid::from_pts(dots.iter().copied()).render(|_, dot| if *dot { '#' } else { '.' })
 }
 
 fn print(dots: &HashSet<Pt>) -> Result<String, Box<dyn std::error::Error>> {
@@ -87,41 +99,67 @@ impl Solver for Day13 {
 
     type Input = Paper;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         let (dots, folds) = crate::tools::empty_line_delimited_batches(input.lines())
             .tuples()
             .exactly_one()
-            .unwrap_or_else(|_| panic!("invalid input"));
+            .map_err(|_| {
+                ParseError::at(
+                    input,
+                    input,
+                    "expected dots and folds separated by an empty line",
+                )
+            })?;
 
         let dots = dots
             .into_iter()
             .filter(|l| !l.is_empty())
-            .map(|l| l.parse::<Pt>().unwrap())
-            .collect();
+            .map(|l| parse_at(input, l))
+            .collect::<Result<_, _>>()?;
 
         let folds = folds
             .into_iter()
             .filter(|l| !l.is_empty())
             .map(|l| {
-                let (along, value) = l.split_once('=').unwrap();
-                match along.chars().last().unwrap() {
-                    'x' => Fold::X(value.parse::<u32>().unwrap()),
-                    'y' => Fold::Y(value.parse::<u32>().unwrap()),
-                    _ => panic!("invalid fold line {}", l),
+                let (along, value) = split_once_at(input, l, "=")?;
+                match along.chars().last() {
+                    Some('x') => Ok(Fold::X(parse_at(input, value)?)),
+                    Some('y') => Ok(Fold::Y(parse_at(input, value)?)),
+                    _ => Err(ParseError::at(input, along, "expected fold along x or y")),
                 }
             })
-            .collect();
-        Paper(dots, folds)
+            .collect::<Result<_, _>>()?;
+        Ok(Paper(dots, folds))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let first = input.1.first().unwrap();
-        fold_paper(input.0, *first).len().to_string()
+        fold_paper(input.0.clone(), *first).len().to_string()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        let dots = input.1.into_iter().fold(input.0, fold_paper);
+    fn part2(input: &Self::Input) -> Self::Output {
+        let dots = input.1.iter().copied().fold(input.0.clone(), fold_paper);
+
+        // the art is left to read when the letters aren't known
+        let art = print(&dots).unwrap();
+        trace!("folded", paper = art.as_str());
+        crate::ocr::read_dots(dots).unwrap_or(art)
+    }
 
-        print(&dots).unwrap()
+    fn visualize(input: &Self::Input, part: Part) -> Option<Frames<'_>> {
+        let folds = match part {
+            Part::One => &input.1[..input.1.len().min(1)],
+            Part::Two => &input.1[..],
+        };
+        let start = Frame::new(format!("{} dots", input.0.len()), render(&input.0));
+        let folded = folds.iter().scan(input.0.clone(), |dots, along| {
+            *dots = fold_paper(std::mem::take(dots), *along);
+            let caption = match along {
+                Fold::X(x) => format!("fold along x={}: {} dots", x, dots.len()),
+                Fold::Y(y) => format!("fold along y={}: {} dots", y, dots.len()),
+            };
+            Some(Frame::new(caption, render(dots)))
+        });
+        Some(Box::new(std::iter::once(start).chain(folded)))
     }
 }
@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day14, 14);
+register!(Day14);
 
 sample!(
     Day14,
@@ -69,6 +70,8 @@ impl Template {
                     .or_insert(*freq);
             }
         });
+        // pairs that were all split are gone
+        new_frequencies.retain(|_, freq| *freq > 0);
         self.frequencies = new_frequencies;
     }
 
@@ -95,48 +98,58 @@ impl Solver for Day14 {
 
     type Input = (Vec<Element>, HashMap<Pair, [Pair; 2]>);
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         let (template, rules) = crate::tools::empty_line_delimited_batches(input.lines())
             .tuples()
             .exactly_one()
-            .ok()
-            .unwrap();
+            .map_err(|_| {
+                ParseError::at(
+                    input,
+                    input,
+                    "expected a template and rules separated by an empty line",
+                )
+            })?;
 
         let template = template
             .into_iter()
             .exactly_one()
-            .ok()
-            .unwrap()
+            .map_err(|_| ParseError::at(input, input, "expected a single template line"))?
             .chars()
             .collect();
 
         let rules = rules
             .into_iter()
             .map(|l| {
-                let (tpl, el) = l.split_once(" -> ").unwrap();
-
-                let (a, b) = tpl.chars().tuples().exactly_one().ok().unwrap();
-                let c = el.chars().exactly_one().ok().unwrap();
-
-                ([a, b], [[a, c], [c, b]])
+                let (tpl, el) = split_once_at(input, l, " -> ")?;
+
+                let (a, b) = tpl
+                    .chars()
+                    .tuples()
+                    .exactly_one()
+                    .map_err(|_| ParseError::at(input, tpl, "expected a pair of elements"))?;
+                let c = el
+                    .chars()
+                    .exactly_one()
+                    .map_err(|_| ParseError::at(input, el, "expected a single element"))?;
+
+                Ok(([a, b], [[a, c], [c, b]]))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        (template, rules)
+        Ok((template, rules))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        let mut tpl = Template::new(input.0);
-        for _ in 0..10 {
-            log::debug!("{:?}", tpl);
+    fn part1(input: &Self::Input) -> Self::Output {
+        let mut tpl = Template::new(input.0.clone());
+        for step in 0..10 {
             tpl.step(&input.1);
+            trace!("step", step = step + 1, pairs = tpl.frequencies.len());
         }
-        log::debug!("{:?}", tpl);
         tpl.solve()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        let mut tpl = Template::new(input.0);
+    fn part2(input: &Self::Input) -> Self::Output {
+        let mut tpl = Template::new(input.0.clone());
         for _ in 0..40 {
             tpl.step(&input.1);
         }
@@ -152,7 +165,7 @@ mod test {
 
     #[test]
     fn test_sample() {
-        let (els, rules) = Day14::parse(Day14::CONTENT);
+        let (els, rules) = Day14::parse(Day14::SAMPLES[0].content).unwrap();
         let mut tpl = Template::new(els);
 
         tpl.step(&rules);
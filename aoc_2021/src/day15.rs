@@ -4,6 +4,7 @@ use std::{cmp::Reverse, collections::BinaryHeap};
 
 use aoc_lib::*;
 day!(Day15, 15);
+register!(Day15, Main, AStar, Dijkstra);
 
 sample!(
     Day15,
@@ -24,7 +25,7 @@ sample!(
 type Cavern = crate::grid::Grid<u8>;
 type Pt = crate::grid::Pt<u32>;
 
-fn pathfinding_astar(cavern: Cavern) -> usize {
+fn pathfinding_astar(cavern: &Cavern) -> usize {
     let w = cavern.width() as u32;
     let h = cavern.height() as u32;
     let end = Pt::new(w - 1, h - 1);
@@ -41,7 +42,8 @@ fn pathfinding_astar(cavern: Cavern) -> usize {
     path.unwrap().1
 }
 
-fn pathfinding_dijkstra(cavern: Cavern) -> usize {
+// the cheapest path from the top left to the bottom right, and its risk
+fn pathfinding_dijkstra(cavern: &Cavern) -> (Vec<Pt>, usize) {
     let w = cavern.width() as u32;
     let h = cavern.height() as u32;
     let end = Pt::new(w - 1, h - 1);
@@ -54,7 +56,7 @@ fn pathfinding_dijkstra(cavern: Cavern) -> usize {
         },
         |pt| *pt == end,
     );
-    path.unwrap().1
+    path.unwrap()
 }
 
 // Priority queue "nodes".
@@ -99,7 +101,7 @@ where
     None
 }
 
-fn manual_dijkstra(cavern: Cavern) -> usize {
+fn manual_dijkstra(cavern: &Cavern) -> usize {
     let width = cavern.width();
     let height = cavern.height();
     dijkstra(
@@ -115,7 +117,7 @@ fn manual_dijkstra(cavern: Cavern) -> usize {
     .unwrap()
 }
 
-fn extend(cavern: Cavern) -> Cavern {
+fn extend(cavern: &Cavern) -> Cavern {
     let width = cavern.width();
     let height = cavern.height();
 
@@ -140,20 +142,56 @@ impl Solver for Day15 {
 
     type Input = Cavern;
 
-    fn parse(input: &str) -> Self::Input {
-        Cavern::from_row_iter(input.lines().map(|l| {
-            l.chars()
-                .map(|c| c.to_string().parse::<u8>().unwrap())
-                .collect()
-        }))
+    const SHARES_INPUT: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rows = input
+            .lines()
+            .map(|l| {
+                l.char_indices()
+                    .map(|(idx, c)| {
+                        c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                            ParseError::at(input, &l[idx..idx + c.len_utf8()], "not a digit")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Cavern::from_row_iter(rows.into_iter()))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         manual_dijkstra(input)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        manual_dijkstra(extend(input))
+    fn part2(input: &Self::Input) -> Self::Output {
+        manual_dijkstra(&extend(input))
+    }
+
+    // the path found is drawn one step at a time
+    fn visualize(input: &Self::Input, part: Part) -> Option<Frames<'_>> {
+        let cavern = match part {
+            Part::One => input.clone(),
+            Part::Two => extend(input),
+        };
+        let (path, _) = pathfinding_dijkstra(&cavern);
+        let mut on_path = crate::grid::Grid::from_iter(
+            cavern.width(),
+            vec![false; cavern.width() * cavern.height()],
+        );
+        let mut risk = 0;
+        let steps = path.into_iter().enumerate().map(move |(idx, pt)| {
+            if idx > 0 {
+                risk += cavern[pt] as usize;
+            }
+            on_path[pt] = true;
+            let content = cavern.render(|pt, r| match on_path[pt] {
+                true => '#',
+                false => (b'0' + r) as char,
+            });
+            Frame::new(format!("step {}: risk {}", idx, risk), content)
+        });
+        Some(Box::new(steps))
     }
 }
 
@@ -164,16 +202,18 @@ impl Solver<AStar> for Day15 {
 
     type Input = Cavern;
 
-    fn parse(input: &str) -> Self::Input {
+    const SHARES_INPUT: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         <Day15 as Solver>::parse(input)
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         pathfinding_astar(input)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        pathfinding_astar(extend(input))
+    fn part2(input: &Self::Input) -> Self::Output {
+        pathfinding_astar(&extend(input))
     }
 }
 
@@ -184,15 +224,17 @@ impl Solver<Dijkstra> for Day15 {
 
     type Input = Cavern;
 
-    fn parse(input: &str) -> Self::Input {
+    const SHARES_INPUT: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         <Day15 as Solver>::parse(input)
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        pathfinding_dijkstra(input)
+    fn part1(input: &Self::Input) -> Self::Output {
+        pathfinding_dijkstra(input).1
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        pathfinding_dijkstra(extend(input))
+    fn part2(input: &Self::Input) -> Self::Output {
+        pathfinding_dijkstra(&extend(input)).1
     }
 }
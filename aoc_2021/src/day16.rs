@@ -3,11 +3,33 @@ use bitvec::prelude::*;
 use itertools::Itertools;
 
 day!(Day16, 16);
+register!(Day16, Main, Bitter);
 
 type Bits = BitVec<Msb0, u8>;
 type Slice = BitSlice<Msb0, u8>;
 
-sample!(Day16, "A0016C880162017C3686B18A3D4780", "31");
+sample!(
+    Day16,
+    Example::new("literal", "D2FE28").part1("6").part2("2021"),
+    Example::new("operator with length", "38006F45291200")
+        .part1("9")
+        .part2("1"),
+    Example::new("operator with count", "EE00D40C823060")
+        .part1("14")
+        .part2("3"),
+    Example::new("nested operators", "8A004A801A8002F478").part1("16"),
+    Example::new("operator with two operators", "620080001611562C8802118E34").part1("12"),
+    Example::new("operator with length type", "C0015000016115A2E0802F182340").part1("23"),
+    Example::new("five literals", "A0016C880162017C3686B18A3D4780").part1("31"),
+    Example::new("sum", "C200B40A82").part2("3"),
+    Example::new("product", "04005AC33890").part2("54"),
+    Example::new("minimum", "880086C3E88112").part2("7"),
+    Example::new("maximum", "CE00C43D881120").part2("9"),
+    Example::new("less than", "D8005AC2A8F0").part2("1"),
+    Example::new("greater than", "F600BC2D8F").part2("0"),
+    Example::new("equal", "9C005AC2F8F0").part2("0"),
+    Example::new("nested equal", "9C0141080250320F1802104A08").part2("1"),
+);
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum Op {
@@ -61,14 +83,14 @@ fn parse_varint(bits: &Slice) -> (usize, &Slice) {
 }
 
 fn parse_literal(bits: &Slice) -> (Token, &Slice) {
-    log::debug!("parse lit: {:?}", bits);
     let (lit, rest) = parse_varint(bits);
+    trace!("literal", value = lit, bits = bits.len() - rest.len());
 
     (Token::Literal(lit), rest)
 }
 
 fn parse_op(op: u8, bits: &Slice) -> (Token, &Slice) {
-    log::debug!("parse op {}: {:?}", op, bits);
+    trace!("operator", op = op, bits = bits.len());
     let (length_bit, rest) = bits.split_at(1);
     if *length_bit.get(0).unwrap() {
         // 11 bits
@@ -148,25 +170,33 @@ impl Solver for Day16 {
 
     type Input = Bits;
 
-    fn parse(input: &str) -> Self::Input {
-        input
-            .trim()
-            .chars()
-            .map(|c| u8::from_str_radix(&c.to_string(), 16).unwrap())
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let hex = input.trim();
+        let nibbles = hex
+            .char_indices()
+            .map(|(idx, c)| {
+                c.to_digit(16).map(|d| d as u8).ok_or_else(|| {
+                    ParseError::at(input, &hex[idx..idx + c.len_utf8()], "not a hex digit")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(nibbles
+            .into_iter()
             .tuples()
             .map(|(a, b)| Bits::from_element(a << 4 | b))
             .fold(Bits::new(), |mut v, b| {
                 v.extend_from_bitslice(&b);
                 v
-            })
+            }))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        Packet::parse(&input).0.sum_versions()
+    fn part1(input: &Self::Input) -> Self::Output {
+        Packet::parse(input).0.sum_versions()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        Packet::parse(&input).0.compute()
+    fn part2(input: &Self::Input) -> Self::Output {
+        Packet::parse(input).0.compute()
     }
 }
 
@@ -234,14 +264,14 @@ mod bitbit {
 
         #[test]
         fn test_parse_lit() {
-            let bits = <Day16 as Solver<Bitter>>::parse("D2FE28");
+            let bits = <Day16 as Solver<Bitter>>::parse("D2FE28").unwrap();
             let lit = parse_packet(&mut BigEndianReader::new(&bits));
             assert_eq!(lit, Packet(6, Token::Literal(2021)));
         }
 
         #[test]
         fn test_parse_op() {
-            let bits = <Day16 as Solver<Bitter>>::parse("38006F45291200");
+            let bits = <Day16 as Solver<Bitter>>::parse("38006F45291200").unwrap();
             let packet = parse_packet(&mut BigEndianReader::new(&bits));
             assert_eq!(
                 packet,
@@ -253,7 +283,7 @@ mod bitbit {
                     )
                 )
             );
-            let bits = <Day16 as Solver<Bitter>>::parse("EE00D40C823060");
+            let bits = <Day16 as Solver<Bitter>>::parse("EE00D40C823060").unwrap();
             let packet = parse_packet(&mut BigEndianReader::new(&bits));
             assert_eq!(
                 packet,
@@ -280,20 +310,24 @@ impl Solver<Bitter> for Day16 {
 
     type Input = Vec<u8>;
 
-    fn parse(input: &str) -> Self::Input {
-        input
-            .chars()
-            .tuples()
-            .map(|(a, b)| u8::from_str_radix(&String::from_iter([a, b]), 16).unwrap())
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let hex = input.trim();
+        (0..hex.len() / 2)
+            .map(|idx| {
+                let byte = hex
+                    .get(idx * 2..idx * 2 + 2)
+                    .ok_or_else(|| ParseError::at(input, hex, "not a hex string"))?;
+                u8::from_str_radix(byte, 16).map_err(|e| ParseError::at(input, byte, e))
+            })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        bitbit::parse(&input).sum_versions()
+    fn part1(input: &Self::Input) -> Self::Output {
+        bitbit::parse(input).sum_versions()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        bitbit::parse(&input).compute()
+    fn part2(input: &Self::Input) -> Self::Output {
+        bitbit::parse(input).compute()
     }
 }
 
@@ -303,13 +337,13 @@ mod test {
 
     #[test]
     fn test_parse_literal() {
-        let bits = <Day16 as Solver>::parse("D2FE28");
+        let bits = <Day16 as Solver>::parse("D2FE28").unwrap();
         let (packet, _) = Packet::parse(&bits);
         assert_eq!(packet, Packet(6, Token::Literal(2021)));
     }
     #[test]
     fn test_parse_op() {
-        let bits = <Day16 as Solver>::parse("38006F45291200");
+        let bits = <Day16 as Solver>::parse("38006F45291200").unwrap();
         let (packet, _) = Packet::parse(&bits);
         assert_eq!(
             packet,
@@ -321,7 +355,7 @@ mod test {
                 )
             )
         );
-        let bits = <Day16 as Solver>::parse("EE00D40C823060");
+        let bits = <Day16 as Solver>::parse("EE00D40C823060").unwrap();
         let (packet, _) = Packet::parse(&bits);
         assert_eq!(
             packet,
@@ -3,6 +3,7 @@ use itertools::Itertools;
 use crate::grid::Pt;
 use aoc_lib::*;
 day!(Day17, 17);
+register!(Day17);
 
 sample!(Day17, "target area: x=20..30, y=-10..-5", "45", "112");
 
@@ -58,21 +59,21 @@ impl Solver for Day17 {
 
     type Input = Area;
 
-    fn parse(input: &str) -> Self::Input {
-        let (_, input) = input.split_once(':').unwrap();
-        let (x, y) = input.trim().split_once(", ").unwrap();
-        let (_, x) = x.split_once('=').unwrap();
-        let (_, y) = y.split_once('=').unwrap();
-        let (xmin, xmax) = x.split_once("..").unwrap();
-        let (ymin, ymax) = y.split_once("..").unwrap();
-
-        Area(
-            Pt::new(xmin.parse().unwrap(), ymin.parse().unwrap()),
-            Pt::new(xmax.parse().unwrap(), ymax.parse().unwrap()),
-        )
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (_, area) = split_once_at(input, input, ":")?;
+        let (x, y) = split_once_at(input, area.trim(), ", ")?;
+        let (_, x) = split_once_at(input, x, "=")?;
+        let (_, y) = split_once_at(input, y, "=")?;
+        let (xmin, xmax) = split_once_at(input, x, "..")?;
+        let (ymin, ymax) = split_once_at(input, y.trim(), "..")?;
+
+        Ok(Area(
+            Pt::new(parse_at(input, xmin)?, parse_at(input, ymin)?),
+            Pt::new(parse_at(input, xmax)?, parse_at(input, ymax)?),
+        ))
     }
 
-    fn part1(area: Self::Input) -> Self::Output {
+    fn part1(area: &Self::Input) -> Self::Output {
         // whatever vertical velocity we start with (Vi), when the probe comes back down,
         //   it will have velocity -(Vi + 1) at y=0
         // So the maximum vertical velocity we can get is the distance from 0 to the lower part of the area - 1.
@@ -84,12 +85,12 @@ impl Solver for Day17 {
         let vi = dist - 1;
         let max_x = area.1.x + 1;
         (0..max_x)
-            .flat_map(|x| is_solution(Velocity(x, vi), &area))
+            .flat_map(|x| is_solution(Velocity(x, vi), area))
             .max()
             .unwrap() as usize
     }
 
-    fn part2(area: Self::Input) -> Self::Output {
+    fn part2(area: &Self::Input) -> Self::Output {
         // we know this is the maximum velocity we can start with
         let max_y = area.0.y.abs();
         // at this velocity, after a single step, we've overshot the target
@@ -97,7 +98,7 @@ impl Solver for Day17 {
 
         (0..max_x)
             .cartesian_product(-max_y..=max_y)
-            .filter(|(x, y)| is_solution(Velocity(*x, *y), &area).is_some())
+            .filter(|(x, y)| is_solution(Velocity(*x, *y), area).is_some())
             .unique()
             .count()
     }
@@ -110,7 +111,7 @@ mod test {
     use super::*;
     #[test]
     fn test_sample() {
-        let area = Day17::parse(<Day17 as Sample>::CONTENT);
+        let area = Day17::parse(Day17::SAMPLES[0].content).unwrap();
 
         assert_eq!(is_solution(Velocity(7, 2), &area), Some(3));
         assert_eq!(is_solution(Velocity(6, 3), &area), Some(6));
@@ -120,6 +121,6 @@ mod test {
         assert_eq!(is_solution(Velocity(0, 10), &area), None);
         assert_eq!(is_solution(Velocity(100, 0), &area), None);
 
-        assert_eq!(Day17::part1(area), 45);
+        assert_eq!(Day17::part1(&area), 45);
     }
 }
@@ -4,6 +4,7 @@ use aoc_lib::*;
 use itertools::Itertools;
 use text_trees::StringTreeNode;
 day!(Day18, 18);
+register!(Day18);
 
 sample!(
     Day18,
@@ -89,10 +90,10 @@ impl Node {
     fn reduce(&mut self) {
         loop {
             if self.explode().is_some() {
-                log::debug!("exp: {}", self);
+                trace!("explode", number = self.to_string());
                 continue;
             } else if self.split() {
-                log::debug!("spl: {}", self);
+                trace!("split", number = self.to_string());
                 continue;
             } else {
                 break;
@@ -131,40 +132,40 @@ impl Display for Node {
     }
 }
 
-fn parse_node(input: &str) -> (Node, &str) {
-    match input.chars().next().unwrap() {
-        '[' => parse_branch(input),
-        '0'..='9' => {
-            let n = String::from_iter(input.chars().take_while(|c| ('0'..='9').contains(c)));
-            let (_, rest) = input.split_at(n.len());
-            (Node::Leaf(n.parse::<u8>().unwrap()), rest)
+// `input` is the whole puzzle input, only used to locate errors
+fn parse_node<'a>(input: &str, s: &'a str) -> Result<(Node, &'a str), ParseError> {
+    match s.chars().next() {
+        Some('[') => parse_branch(input, s),
+        Some('0'..='9') => {
+            let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (n, rest) = s.split_at(len);
+            Ok((Node::Leaf(parse_at(input, n)?), rest))
         }
-
-        invalid => panic!("unexpected {}", invalid),
+        _ => Err(ParseError::at(
+            input,
+            s.get(..1).unwrap_or(s),
+            "expected `[` or a number",
+        )),
     }
 }
 
-fn parse_branch(input: &str) -> (Node, &str) {
-    let (c, rest) = input.split_at(1);
-    if c != "[" {
-        panic!("expected [ got {}", c);
-    }
-    let (first, rest) = parse_node(rest);
-    let (c, rest) = rest.split_at(1);
-    if c != "," {
-        panic!("expected , got {}", c);
-    }
-    let (second, rest) = parse_node(rest);
-    let (c, rest) = rest.split_at(1);
-    if c != "]" {
-        panic!("expected ] got {}", c);
-    }
-    (Node::branch(first, second), rest)
+fn expect<'a>(input: &str, s: &'a str, c: char) -> Result<&'a str, ParseError> {
+    s.strip_prefix(c)
+        .ok_or_else(|| ParseError::at(input, s.get(..1).unwrap_or(s), format!("expected `{}`", c)))
+}
+
+fn parse_branch<'a>(input: &str, s: &'a str) -> Result<(Node, &'a str), ParseError> {
+    let rest = expect(input, s, '[')?;
+    let (first, rest) = parse_node(input, rest)?;
+    let rest = expect(input, rest, ',')?;
+    let (second, rest) = parse_node(input, rest)?;
+    let rest = expect(input, rest, ']')?;
+    Ok((Node::branch(first, second), rest))
 }
 
-fn parse(input: &str) -> Node {
-    let (node, _) = parse_branch(input);
-    node
+fn parse_line(input: &str, line: &str) -> Result<Node, ParseError> {
+    let (node, _) = parse_branch(input, line)?;
+    Ok(node)
 }
 
 fn sum_vec(fish: Vec<Node>) -> Node {
@@ -176,22 +177,23 @@ impl Solver for Day18 {
 
     type Input = Vec<Node>;
 
-    fn parse(input: &str) -> Self::Input {
-        input.lines().map(|l| parse(l.trim())).collect()
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|l| parse_line(input, l.trim())).collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        sum_vec(input).magnitude()
+    fn part1(input: &Self::Input) -> Self::Output {
+        sum_vec(input.clone()).magnitude()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input
-            .into_iter()
+            .iter()
             .combinations(2)
             .flat_map(|n| {
                 if let [left, right] = n.as_slice() {
+                    let (left, right) = (Node::clone(left), Node::clone(right));
                     let a = Node::sum(left.clone(), right.clone()).magnitude();
-                    let b = Node::sum(right.clone(), left.clone()).magnitude();
+                    let b = Node::sum(right, left).magnitude();
                     vec![a, b]
                 } else {
                     unreachable!();
@@ -208,6 +210,10 @@ mod test {
 
     use super::*;
 
+    fn parse(s: &str) -> Node {
+        parse_line(s, s).unwrap()
+    }
+
     #[test]
     fn test_parse() {
         assert_eq!(parse("[1,2]"), Node::branch(Node::Leaf(1), Node::Leaf(2)));
@@ -269,7 +275,8 @@ mod test {
 [2,2]
 [3,3]
 [4,4]",
-        );
+        )
+        .unwrap();
         assert_eq!(sum_vec(fish), parse("[[[[1,1],[2,2]],[3,3]],[4,4]]"));
 
         let fish = <Day18 as Solver>::parse(
@@ -278,7 +285,8 @@ mod test {
 [3,3]
 [4,4]
 [5,5]",
-        );
+        )
+        .unwrap();
         assert_eq!(sum_vec(fish), parse("[[[[3,0],[5,3]],[4,4]],[5,5]]"));
 
         let fish = <Day18 as Solver>::parse(
@@ -288,13 +296,14 @@ mod test {
 [4,4]
 [5,5]
 [6,6]",
-        );
+        )
+        .unwrap();
         assert_eq!(sum_vec(fish), parse("[[[[5,0],[7,4]],[5,5]],[6,6]]"));
     }
 
     #[test]
     fn test_sum() {
-        let fish = <Day18 as Solver>::parse(Day18::CONTENT);
+        let fish = <Day18 as Solver>::parse(Day18::SAMPLES[0].content).unwrap();
         assert_eq!(
             sum_vec(fish),
             parse("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]")
@@ -7,6 +7,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day19, 19);
+register!(Day19);
 
 sample!(
     Day19,
@@ -394,13 +395,13 @@ fn pairwise_transforms(scanners: Vec<Scanner>) -> HashMap<(usize, usize), Coordi
     result
 }
 
-fn solve_scanner_orientations(scanners: Vec<Scanner>) -> Vec<(Scanner, CoordinateTransform)> {
-    let xforms = pairwise_transforms(scanners.clone());
+fn solve_scanner_orientations(scanners: &[Scanner]) -> Vec<(Scanner, CoordinateTransform)> {
+    let xforms = pairwise_transforms(scanners.to_vec());
     let base = scanners[0].clone();
     let mut result = vec![(base, CoordinateTransform::default())];
-    for (idx, s) in scanners.into_iter().enumerate().skip(1) {
+    for (idx, s) in scanners.iter().enumerate().skip(1) {
         let cs = xforms.get(&(0, idx)).unwrap();
-        result.push((cs.apply(&s), cs.clone()));
+        result.push((cs.apply(s), cs.clone()));
     }
     result
 }
@@ -410,29 +411,31 @@ impl Solver for Day19 {
 
     type Input = Vec<Scanner>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         crate::tools::empty_line_delimited_batches(input.lines())
             .map(|lines| {
-                Scanner(
-                    lines
-                        .into_iter()
-                        .skip(1)
-                        .map(|l| {
-                            let (x, y, z) =
-                                l.split_terminator(',').tuples().exactly_one().ok().unwrap();
-                            Vector([
-                                x.parse::<i32>().unwrap(),
-                                y.parse::<i32>().unwrap(),
-                                z.parse::<i32>().unwrap(),
-                            ])
-                        })
-                        .collect(),
-                )
+                let beacons = lines
+                    .into_iter()
+                    .skip(1)
+                    .map(|l| {
+                        let (x, y, z) = l
+                            .split_terminator(',')
+                            .tuples()
+                            .exactly_one()
+                            .map_err(|_| ParseError::at(input, l, "expected `x,y,z`"))?;
+                        Ok(Vector([
+                            parse_at(input, x)?,
+                            parse_at(input, y)?,
+                            parse_at(input, z)?,
+                        ]))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Scanner(beacons))
             })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         solve_scanner_orientations(input)
             .into_iter()
             .flat_map(|(s, _)| s.0)
@@ -440,7 +443,7 @@ impl Solver for Day19 {
             .count()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         let origins = solve_scanner_orientations(input)
             .into_iter()
             .map(|(_, cs)| cs.1)
@@ -484,7 +487,7 @@ mod test {
 
     #[test]
     fn test_find_coordinate_system() {
-        let s = &Day19::parse(Day19::CONTENT);
+        let s = &Day19::parse(Day19::SAMPLES[0].content).unwrap();
         let map = pairwise_transforms(s.clone());
 
         let cs0_1 = find_coordinate_xform(&s[0], &s[1]).unwrap();
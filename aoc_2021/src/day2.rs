@@ -1,29 +1,24 @@
-use std::{error::Error, str::FromStr};
-
 use aoc_lib::*;
 
 day!(Day2, 2);
+register!(Day2);
 
+#[derive(Clone, Copy)]
 pub enum Command {
     Down(u32),
     Up(u32),
     Forward(u32),
 }
 
-impl FromStr for Command {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((cmd, value)) = s.split_once(' ') {
-            let value = u32::from_str(value)?;
-            match cmd {
-                "forward" => Ok(Command::Forward(value)),
-                "up" => Ok(Command::Up(value)),
-                "down" => Ok(Command::Down(value)),
-                _ => panic!("unexpected command {}", s),
-            }
-        } else {
-            panic!("unexpected command {}", s)
+impl Command {
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        let (cmd, value) = split_once_at(input, s, " ")?;
+        let value = parse_at(input, value)?;
+        match cmd {
+            "forward" => Ok(Command::Forward(value)),
+            "up" => Ok(Command::Up(value)),
+            "down" => Ok(Command::Down(value)),
+            _ => Err(ParseError::at(input, cmd, "unexpected command")),
         }
     }
 }
@@ -89,23 +84,23 @@ impl Solver for Day2 {
     type Output = i32;
     type Input = Vec<Command>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
             .filter(|l| !l.is_empty())
-            .map(|l| Command::from_str(l).unwrap())
+            .map(|l| Command::parse(input, l))
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let mut pos = Position::new();
-        pos.apply_all(input);
+        pos.apply_all(input.iter().copied());
         pos.0 * pos.1
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         let mut pos = AimedPosition::new();
-        pos.apply_all(input);
+        pos.apply_all(input.iter().copied());
         pos.0 * pos.1
     }
 }
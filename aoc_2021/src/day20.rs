@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day20, 20);
+register!(Day20);
 
 sample!(
     Day20,
@@ -31,13 +32,23 @@ enum Pixel {
 }
 
 impl Pixel {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '#' => Pixel::Light,
-            '.' => Pixel::Dark,
-            _ => panic!("invalid char {}", c),
+            '#' => Some(Pixel::Light),
+            '.' => Some(Pixel::Dark),
+            _ => None,
         }
     }
+
+    fn parse_all(input: &str, s: &str) -> Result<Vec<Self>, ParseError> {
+        s.char_indices()
+            .map(|(idx, c)| {
+                Pixel::from_char(c).ok_or_else(|| {
+                    ParseError::at(input, &s[idx..idx + c.len_utf8()], "expected `#` or `.`")
+                })
+            })
+            .collect()
+    }
 }
 
 type Image = crate::grid::Grid<Pixel>;
@@ -100,17 +111,10 @@ fn enhance(alg: &Algorithm, i: Image, base: Pixel) -> Image {
 
 impl Display for Image {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.rows_iter() {
-            for s in row {
-                let c = match s {
-                    Pixel::Light => '#',
-                    Pixel::Dark => '.',
-                };
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        f.write_str(&self.render(|_, p| match p {
+            Pixel::Light => '#',
+            Pixel::Dark => '.',
+        }))
     }
 }
 
@@ -119,41 +123,46 @@ impl Solver for Day20 {
 
     type Input = Scan;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         let (alg, image) = crate::tools::empty_line_delimited_batches(input.lines())
             .tuples()
             .exactly_one()
-            .ok()
-            .unwrap();
-
-        Scan(
-            Algorithm(
-                alg.into_iter()
-                    .join("")
-                    .chars()
-                    .map(Pixel::from_char)
-                    .collect(),
-            ),
-            Image::from_row_iter(
-                image
-                    .into_iter()
-                    .map(|s| s.chars().map(Pixel::from_char).collect()),
-            ),
-        )
+            .map_err(|_| {
+                ParseError::at(
+                    input,
+                    input,
+                    "expected an algorithm and an image separated by an empty line",
+                )
+            })?;
+
+        let alg = alg
+            .into_iter()
+            .map(|l| Pixel::parse_all(input, l))
+            .flatten_ok()
+            .collect::<Result<_, _>>()?;
+        let image = image
+            .into_iter()
+            .map(|l| Pixel::parse_all(input, l))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Scan(
+            Algorithm(alg),
+            Image::from_row_iter(image.into_iter()),
+        ))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let dark_digit = input.0[&[Pixel::Dark; 9]];
         enhance(
             &input.0,
-            enhance(&input.0, input.1, Pixel::Dark),
-            dbg!(dark_digit),
+            enhance(&input.0, input.1.clone(), Pixel::Dark),
+            dark_digit,
         )
         .lit()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        let mut img = input.1;
+    fn part2(input: &Self::Input) -> Self::Output {
+        let mut img = input.1.clone();
         let mut inf_pixel = Pixel::Dark;
         for _ in 0..50 {
             img = enhance(&input.0, img, inf_pixel);
@@ -161,6 +170,22 @@ impl Solver for Day20 {
         }
         img.lit()
     }
+
+    fn visualize(input: &Self::Input, part: Part) -> Option<Frames<'_>> {
+        let steps = match part {
+            Part::One => 2,
+            Part::Two => 50,
+        };
+        let start = Frame::new(format!("{} lit", input.1.lit()), input.1.to_string());
+        let mut inf_pixel = Pixel::Dark;
+        let enhanced = (1..=steps).scan(input.1.clone(), move |img, s| {
+            *img = enhance(&input.0, img.clone(), inf_pixel);
+            inf_pixel = input.0[&[inf_pixel; 9]];
+            let caption = format!("step {}: {} lit", s, img.lit());
+            Some(Frame::new(caption, img.to_string()))
+        });
+        Some(Box::new(std::iter::once(start).chain(enhanced)))
+    }
 }
 
 #[cfg(test)]
@@ -170,16 +195,16 @@ mod test {
 
     #[test]
     fn test_index_algorithm() {
-        let Scan(alg, _) = Day20::parse(Day20::CONTENT);
+        let Scan(alg, _) = Day20::parse(Day20::SAMPLES[0].content).unwrap();
 
-        let n = "...#...#.".chars().map(Pixel::from_char).collect_vec();
+        let n = "...#...#.".chars().flat_map(Pixel::from_char).collect_vec();
 
         assert_eq!(alg[&n], Pixel::Light);
     }
 
     #[test]
     fn test_step() {
-        let Scan(alg, img) = Day20::parse(Day20::CONTENT);
+        let Scan(alg, img) = Day20::parse(Day20::SAMPLES[0].content).unwrap();
         let expected = "#
 
 .##.##.
@@ -189,7 +214,7 @@ mod test {
 .#..##.
 ..##..#
 ...#.#.";
-        let Scan(_, expected) = Day20::parse(expected);
+        let Scan(_, expected) = Day20::parse(expected).unwrap();
         let img = enhance(&alg, img, Pixel::Dark);
         assert_eq!(
             img.rows_iter().collect_vec(),
@@ -208,7 +233,7 @@ mod test {
 ...##.##.
 ....###..";
 
-        let Scan(_, expected) = Day20::parse(expected);
+        let Scan(_, expected) = Day20::parse(expected).unwrap();
         let img = enhance(&alg, img, Pixel::Dark);
         assert_eq!(
             img.rows_iter().collect_vec(),
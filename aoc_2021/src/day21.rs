@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day21, 21);
+register!(Day21);
 
 sample!(
     Day21,
@@ -131,7 +132,9 @@ impl QuantumUniverse {
         while !self.games.is_empty() {
             self.step_and_collapse(0);
             self.step_and_collapse(1);
+            metrics::observe("game states per round", self.games.len() as u64);
         }
+        metrics::count("universes", (self.wins[0] + self.wins[1]) as u64);
     }
 }
 
@@ -140,21 +143,25 @@ impl Solver for Day21 {
 
     type Input = (u8, u8);
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
-            .map(|l| l.chars().last().unwrap().to_string().parse::<u8>().unwrap())
+            .map(|l| {
+                let (_, pos) = split_once_at(input, l, ": ")?;
+                parse_at::<u8>(input, pos.trim())
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .tuples()
             .exactly_one()
-            .ok()
-            .unwrap()
+            .map_err(|_| ParseError::at(input, input, "expected exactly 2 players"))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         solve1(input.0, input.1)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         let mut q = QuantumUniverse::new(input.0, input.1);
         q.solve();
         q.wins[0].max(q.wins[1])
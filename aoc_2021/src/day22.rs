@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day22, 22);
+register!(Day22);
 
 sample!(
     Day22,
@@ -318,37 +319,39 @@ impl Solver for Day22 {
 
     type Input = Vec<Instr>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
             .map(|l| {
-                let (on_off, ranges) = l.split_once(' ').unwrap();
+                let (on_off, ranges) = split_once_at(input, l, " ")?;
                 let turn_on = match on_off {
                     "on" => true,
                     "off" => false,
-                    _ => panic!("unexpected instruction {}", on_off),
+                    _ => return Err(ParseError::at(input, on_off, "unexpected instruction")),
                 };
 
-                fn parse_range(s: &str) -> RangeInclusive<i32> {
-                    let (_, s) = s.split_once('=').unwrap();
-                    let (from, to) = s.split_once("..").unwrap();
-                    let from = from.parse::<i32>().unwrap();
-                    let to = to.parse::<i32>().unwrap();
-                    from..=to
-                }
+                let parse_range = |s| -> Result<RangeInclusive<i32>, ParseError> {
+                    let (_, s) = split_once_at(input, s, "=")?;
+                    let (from, to) = split_once_at(input, s, "..")?;
+                    Ok(parse_at(input, from)?..=parse_at(input, to)?)
+                };
                 let (x, y, z) = ranges
                     .split_terminator(',')
                     .tuples()
                     .exactly_one()
-                    .ok()
-                    .unwrap();
-
-                Instr(turn_on, parse_range(x), parse_range(y), parse_range(z))
+                    .map_err(|_| ParseError::at(input, ranges, "expected `x=..,y=..,z=..`"))?;
+
+                Ok(Instr(
+                    turn_on,
+                    parse_range(x)?,
+                    parse_range(y)?,
+                    parse_range(z)?,
+                ))
             })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         fn included(r: &RangeInclusive<i32>) -> bool {
             let (from, to) = r.clone().into_inner();
             (-50..=50).contains(&from) && (-50..=50).contains(&to)
@@ -356,14 +359,15 @@ impl Solver for Day22 {
 
         solve(
             input
-                .into_iter()
-                .filter(|r| included(&r.1) && included(&r.2) && included(&r.3)),
+                .iter()
+                .filter(|r| included(&r.1) && included(&r.2) && included(&r.3))
+                .cloned(),
         )
         .count()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        solve(input.into_iter()).count()
+    fn part2(input: &Self::Input) -> Self::Output {
+        solve(input.iter().cloned()).count()
     }
 }
 
@@ -377,7 +381,7 @@ mod test {
 on x=11..13,y=11..13,z=11..13
 off x=9..11,y=9..11,z=9..11
 on x=10..10,y=10..10,z=10..10";
-        let input = Day22::parse(input);
+        let input = Day22::parse(input).unwrap();
         let tree = solve(input.clone().into_iter().map_into().take(1));
         assert_eq!(tree.count(), 27);
 
@@ -397,6 +401,18 @@ on x=10..10,y=10..10,z=10..10";
         assert_eq!(tree.count(), 39);
     }
 
+    #[test]
+    fn test_parse_error() {
+        let input = "on x=10..12,y=10..12,z=10..12
+of x=9..11,y=9..11,z=9..11";
+        let e = Day22::parse(input).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "of"));
+
+        let input = "on x=10..12,y=10..1a,z=10..12";
+        let e = Day22::parse(input).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 19, "1a"));
+    }
+
     macro_rules! bulbs {
         ($range: expr, $lit: literal) => {
             RangeTree::new($range, Entry::Bulb($lit))
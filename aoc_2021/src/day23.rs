@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day23, 23);
+register!(Day23);
 
 type Pt = crate::grid::Pt<u8>;
 
@@ -27,13 +28,13 @@ pub enum Amphipod {
 }
 
 impl Amphipod {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            'A' => Amphipod::A,
-            'B' => Amphipod::B,
-            'C' => Amphipod::C,
-            'D' => Amphipod::D,
-            c => panic!("not an amphipod: {}", c),
+            'A' => Some(Amphipod::A),
+            'B' => Some(Amphipod::B),
+            'C' => Some(Amphipod::C),
+            'D' => Some(Amphipod::D),
+            _ => None,
         }
     }
     fn cost(&self) -> usize {
@@ -264,13 +265,20 @@ impl<const D: usize> IndexMut<Pt> for Cave<D> {
 }
 
 fn solve<const D: usize>(input: [Room<D>; 4]) -> usize {
+    let mut expanded = 0;
     let (_, cost) = pathfinding::directed::dijkstra::dijkstra(
         &Cave::<D>::new(input),
-        |cave| cave.neighbour_caves().collect_vec(), // damn
+        |cave| {
+            let next = cave.neighbour_caves().collect_vec(); // damn
+            trace!("expanded", moves = next.len());
+            expanded += 1;
+            next
+        },
         |cave| cave.is_solved(),
     )
     .unwrap();
 
+    metrics::count("states expanded", expanded);
     cost
 }
 
@@ -301,30 +309,32 @@ impl Solver for Day23 {
 
     type Input = [Room<2>; 4];
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         let mut rooms = [Room::empty(); 4];
-        input
-            .lines()
-            .skip(2)
-            .take(2)
-            .enumerate()
-            .for_each(|(row, l)| {
-                l[3..=10]
-                    .split_terminator('#')
-                    .take(4)
-                    .map(|s| Amphipod::from_char(s.chars().next().unwrap()))
-                    .enumerate()
-                    .for_each(|(idx, a)| rooms[idx][row] = Slot::Occupied(a))
-            });
-        rooms
+        for (row, l) in input.lines().skip(2).take(2).enumerate() {
+            // amphipods are at columns 3, 5, 7 and 9
+            for (idx, room) in rooms.iter_mut().enumerate() {
+                let col = 3 + idx * 2;
+                let s = l
+                    .get(col..=col)
+                    .ok_or_else(|| ParseError::at(input, l, "expected 4 amphipods"))?;
+                let a = s
+                    .chars()
+                    .next()
+                    .and_then(Amphipod::from_char)
+                    .ok_or_else(|| ParseError::at(input, s, "not an amphipod"))?;
+                room[row] = Slot::Occupied(a);
+            }
+        }
+        Ok(rooms)
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
-        solve(input)
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve(*input)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        solve(expand(input))
+    fn part2(input: &Self::Input) -> Self::Output {
+        solve(expand(*input))
     }
 }
 
@@ -337,7 +347,7 @@ mod test {
     #[test]
     fn test_parse() {
         assert_eq!(
-            Day23::parse(Day23::CONTENT),
+            Day23::parse(Day23::SAMPLES[0].content).unwrap(),
             [
                 Room([Slot::Occupied(Amphipod::B), Slot::Occupied(Amphipod::A)]),
                 Room([Slot::Occupied(Amphipod::C), Slot::Occupied(Amphipod::D)]),
@@ -350,7 +360,7 @@ mod test {
     #[test]
     fn test_expand() {
         assert_eq!(
-            expand(Day23::parse(Day23::CONTENT)),
+            expand(Day23::parse(Day23::SAMPLES[0].content).unwrap()),
             [
                 Room([
                     Slot::Occupied(Amphipod::B),
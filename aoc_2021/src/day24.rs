@@ -6,8 +6,9 @@ use std::{
 use aoc_lib::*;
 use itertools::Itertools;
 day!(Day24, 24);
+register!(Day24, Main, Direct);
 
-sample!(Day24, "", "");
+sample!(Day24);
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy)]
@@ -19,13 +20,13 @@ enum Reg {
 }
 
 impl Reg {
-    fn from_str(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
         match s {
-            "w" => Reg::w,
-            "x" => Reg::x,
-            "y" => Reg::y,
-            "z" => Reg::z,
-            _ => panic!("invalid register {}", s),
+            "w" => Ok(Reg::w),
+            "x" => Ok(Reg::x),
+            "y" => Ok(Reg::y),
+            "z" => Ok(Reg::z),
+            _ => Err(ParseError::at(input, s, "invalid register")),
         }
     }
 }
@@ -36,10 +37,10 @@ enum Operand {
     Int(isize),
 }
 impl Operand {
-    fn from_str(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
         match s.parse::<isize>() {
-            Ok(int) => Operand::Int(int),
-            _ => Operand::Register(Reg::from_str(s)),
+            Ok(int) => Ok(Operand::Int(int)),
+            _ => Reg::parse(input, s).map(Operand::Register),
         }
     }
 }
@@ -55,21 +56,21 @@ enum Instr {
 }
 
 impl Instr {
-    fn from_str(s: &str) -> Self {
-        let (instr, reg) = s.split_once(' ').unwrap();
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        let (instr, reg) = split_once_at(input, s, " ")?;
         match instr {
-            "inp" => Instr::Inp(Reg::from_str(reg)),
+            "inp" => Ok(Instr::Inp(Reg::parse(input, reg)?)),
             bin => {
-                let (reg, op) = reg.split_once(' ').unwrap();
-                let reg = Reg::from_str(reg);
-                let op = Operand::from_str(op);
+                let (reg, op) = split_once_at(input, reg, " ")?;
+                let reg = Reg::parse(input, reg)?;
+                let op = Operand::parse(input, op)?;
                 match bin {
-                    "mul" => Instr::Mul(reg, op),
-                    "mod" => Instr::Mod(reg, op),
-                    "add" => Instr::Add(reg, op),
-                    "div" => Instr::Div(reg, op),
-                    "eql" => Instr::Eql(reg, op),
-                    _ => panic!("invalid instruction {}", s),
+                    "mul" => Ok(Instr::Mul(reg, op)),
+                    "mod" => Ok(Instr::Mod(reg, op)),
+                    "add" => Ok(Instr::Add(reg, op)),
+                    "div" => Ok(Instr::Div(reg, op)),
+                    "eql" => Ok(Instr::Eql(reg, op)),
+                    _ => Err(ParseError::at(input, bin, "invalid instruction")),
                 }
             }
         }
@@ -148,7 +149,9 @@ pub struct Cipher {
 }
 
 impl Cipher {
-    fn new(program: Vec<Instr>) -> Self {
+    // returns None unless the program is made of 14 steps, each starting with an `inp` instruction
+    fn new(program: Vec<Instr>) -> Option<Self> {
+        let len = program.len();
         let ciphers = program
             .into_iter()
             .batching(|it| match it.next() {
@@ -160,13 +163,25 @@ impl Cipher {
                         std::iter::once(init).chain(rest).collect(),
                     ))
                 }
-                Some(_) => unreachable!(),
+                Some(_) => None,
             })
             .collect_vec();
 
-        assert_eq!(ciphers.len(), 14);
+        if ciphers.len() == 14 && ciphers.iter().map(|c| c.1.len()).sum::<usize>() == len {
+            Some(Self { ciphers })
+        } else {
+            None
+        }
+    }
 
-        Self { ciphers }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let program = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| Instr::parse(input, l.trim()))
+            .collect::<Result<_, _>>()?;
+        Cipher::new(program)
+            .ok_or_else(|| ParseError::at(input, input, "expected 14 steps starting with `inp`"))
     }
 
     fn compute_inputs(&self) -> Vec<HashSet<Alu>> {
@@ -219,6 +234,7 @@ impl Cipher {
                 let (min, max) = valid_digits.into_iter().minmax().into_option().unwrap();
 
                 digits.push((min, max));
+                metrics::observe("valid ALU states per step", valid_outputs.len() as u64);
 
                 Some((cipher.input(), valid_outputs))
             },
@@ -264,6 +280,7 @@ impl Cipher {
                                 })
                                 .or_insert((rmin, rmax));
                         });
+                    metrics::observe("ALU states per step", outputs.len() as u64);
                     outputs
                 });
 
@@ -280,15 +297,17 @@ impl Solver for Day24 {
 
     type Input = Cipher;
 
-    fn parse(input: &str) -> Self::Input {
-        Cipher::new(input.lines().map(Instr::from_str).collect())
+    const SHARES_INPUT: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Cipher::parse(input)
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         input.solve_backtracking().1
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input.solve_backtracking().0
     }
 }
@@ -300,15 +319,17 @@ impl Solver<Direct> for Day24 {
 
     type Input = Cipher;
 
-    fn parse(input: &str) -> Self::Input {
-        Cipher::new(input.lines().map(Instr::from_str).collect())
+    const SHARES_INPUT: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Cipher::parse(input)
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         input.solve_direct().1
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input.solve_direct().0
     }
 }
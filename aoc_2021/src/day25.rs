@@ -3,6 +3,7 @@ use itertools::Itertools;
 use crate::grid::Dir;
 use aoc_lib::*;
 day!(Day25, 25);
+register!(Day25);
 
 sample!(
     Day25,
@@ -28,11 +29,11 @@ enum Cucumber {
 }
 
 impl Cucumber {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '>' => Cucumber::East,
-            'v' => Cucumber::South,
-            _ => panic!("not a cucumber {}", c),
+            '>' => Some(Cucumber::East),
+            'v' => Some(Cucumber::South),
+            _ => None,
         }
     }
     fn dir(&self) -> Dir {
@@ -49,14 +50,15 @@ enum Slot {
     Occupied(Cucumber),
 }
 impl Slot {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '.' => Self::Empty,
-            c => Self::Occupied(Cucumber::from_char(c)),
+            '.' => Some(Self::Empty),
+            c => Cucumber::from_char(c).map(Self::Occupied),
         }
     }
 }
 
+#[derive(Clone)]
 pub struct Ground(Grid);
 
 impl Ground {
@@ -94,6 +96,14 @@ impl Ground {
     fn step(&mut self) -> usize {
         self.step_cucumber(Cucumber::East) + self.step_cucumber(Cucumber::South)
     }
+
+    fn render(&self) -> String {
+        self.0.render(|_, slot| match slot {
+            Slot::Empty => '.',
+            Slot::Occupied(Cucumber::East) => '>',
+            Slot::Occupied(Cucumber::South) => 'v',
+        })
+    }
 }
 
 impl Solver for Day25 {
@@ -101,21 +111,53 @@ impl Solver for Day25 {
 
     type Input = Ground;
 
-    fn parse(input: &str) -> Self::Input {
-        Ground(Grid::from_row_iter(
-            input
-                .lines()
-                .map(|l| l.chars().map(Slot::from_char).collect()),
-        ))
+    // the last day has a single puzzle
+    const PARTS: &'static [Part] = &[Part::One];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rows = input
+            .lines()
+            .map(|l| {
+                l.char_indices()
+                    .map(|(idx, c)| {
+                        Slot::from_char(c).ok_or_else(|| {
+                            ParseError::at(input, &l[idx..idx + c.len_utf8()], "not a cucumber")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Ground(Grid::from_row_iter(rows.into_iter())))
     }
 
-    fn part1(mut input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
+        let mut input = input.clone();
         1 + std::iter::repeat_with(|| input.step())
             .take_while(|s| *s > 0)
             .count()
     }
 
-    fn part2(_input: Self::Input) -> Self::Output {
-        todo!()
+    fn part2(_input: &Self::Input) -> Self::Output {
+        unreachable!("day 25 only has part one")
+    }
+
+    fn visualize(input: &Self::Input, part: Part) -> Option<Frames<'_>> {
+        if part == Part::Two {
+            return None;
+        }
+        let mut ground = input.clone();
+        let mut moving = true;
+        let steps = (1..).map_while(move |s| {
+            if !moving {
+                return None;
+            }
+            let moved = ground.step();
+            moving = moved > 0;
+            Some(Frame::new(
+                format!("step {}: {} moved", s, moved),
+                ground.render(),
+            ))
+        });
+        Some(Box::new(steps))
     }
 }
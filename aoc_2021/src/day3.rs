@@ -1,6 +1,7 @@
 use aoc_lib::*;
 
 day!(Day3, 3);
+register!(Day3);
 
 #[derive(Clone)]
 pub struct Solution(Vec<u32>, usize);
@@ -75,20 +76,24 @@ impl Solver for Day3 {
     type Output = u32;
     type Input = Solution;
 
-    fn parse(input: &str) -> Self::Input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>
     where
         Self: Sized,
     {
         let bits = input
             .lines()
             .filter(|l| !l.is_empty())
-            .map(|line| u32::from_str_radix(line, 2).unwrap())
-            .collect();
-        let width = input.lines().find(|l| !l.is_empty()).unwrap().len();
-        Solution(bits, width)
+            .map(|line| u32::from_str_radix(line, 2).map_err(|e| ParseError::at(input, line, e)))
+            .collect::<Result<_, _>>()?;
+        let width = input
+            .lines()
+            .find(|l| !l.is_empty())
+            .ok_or_else(|| ParseError::at(input, input, "empty input"))?
+            .len();
+        Ok(Solution(bits, width))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let mut zeros = vec![0_u32; input.1];
         let mut ones = vec![0_u32; input.1];
         for bit in 0..input.1 {
@@ -110,7 +115,7 @@ impl Solver for Day3 {
         epsilon * gamma
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         let mut oxygen = input.clone();
         for bit in 0..input.1 {
             oxygen.filter_by_bit_criteria(bit, Criteria::Oxygen);
@@ -3,6 +3,7 @@ use itertools::Itertools;
 use aoc_lib::*;
 
 day!(Day4, 4);
+register!(Day4);
 
 use crate::tools::empty_line_delimited_batches;
 
@@ -101,11 +102,11 @@ where
 pub struct Solution(Vec<u32>, Vec<Board>);
 
 impl Solution {
-    fn solved_boards(self) -> impl Iterator<Item = SolvedBoard> {
+    fn solved_boards(&self) -> impl Iterator<Item = SolvedBoard> {
         SolutionsIter {
-            next_num: self.0.into_iter().enumerate(),
+            next_num: self.0.clone().into_iter().enumerate(),
             next_solved: Vec::new().into_iter(),
-            remaining_boards: self.1,
+            remaining_boards: self.1.clone(),
         }
     }
 }
@@ -140,13 +141,18 @@ impl Solver for Day4 {
     type Output = u32;
     type Input = Solution;
 
-    fn parse(input: &str) -> Self::Input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>
     where
         Self: Sized,
     {
         let mut lines = input.lines();
-        let nums = lines.next().unwrap();
-        let nums = nums.split(',').map(|n| n.parse::<u32>().unwrap()).collect();
+        let nums = lines
+            .next()
+            .ok_or_else(|| ParseError::at(input, input, "empty input"))?;
+        let nums = nums
+            .split(',')
+            .map(|n| parse_at(input, n))
+            .collect::<Result<_, _>>()?;
 
         let boards = empty_line_delimited_batches(lines)
             .map(|board| {
@@ -155,18 +161,18 @@ impl Solver for Day4 {
                     .map(|row| {
                         row.split(' ')
                             .filter(|n| !n.is_empty())
-                            .map(|n| n.parse::<u32>().unwrap())
-                            .collect_vec()
+                            .map(|n| parse_at(input, n))
+                            .collect::<Result<Vec<u32>, _>>()
                     })
-                    .collect_vec();
-                Board::new(cells)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Board::new(cells))
             })
-            .collect_vec();
+            .collect::<Result<_, _>>()?;
 
-        Solution(nums, boards)
+        Ok(Solution(nums, boards))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         input
             .solved_boards()
             .next()
@@ -174,7 +180,7 @@ impl Solver for Day4 {
             .0
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input
             .solved_boards()
             .max_by(|SolvedBoard(_, count), SolvedBoard(_, count2)| count.cmp(count2))
@@ -1,7 +1,6 @@
-use itertools::Itertools;
-
 use aoc_lib::*;
 day!(Day5, 5);
+register!(Day5);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct Pt {
@@ -97,36 +96,33 @@ impl Solver for Day5 {
     type Output = usize;
     type Input = Vec<Line>;
 
-    fn parse(input: &str) -> Self::Input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>
     where
         Self: Sized,
     {
         input
             .lines()
             .map(|l| {
-                let (from, to) = l
-                    .split_once(" -> ")
-                    .unwrap_or_else(|| panic!("invalid line {}", l));
-                fn pt(s: &str) -> Pt {
-                    let (x, y) = s
-                        .split_once(',')
-                        .unwrap_or_else(|| panic!("invalid pt {}", s));
-                    Pt::new(x.parse::<u32>().unwrap(), y.parse::<u32>().unwrap())
-                }
-                Line(pt(from), pt(to))
+                let (from, to) = split_once_at(input, l, " -> ")?;
+                let pt = |s| -> Result<Pt, ParseError> {
+                    let (x, y) = split_once_at(input, s, ",")?;
+                    Ok(Pt::new(parse_at(input, x)?, parse_at(input, y)?))
+                };
+                Ok(Line(pt(from)?, pt(to)?))
             })
-            .collect_vec()
+            .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         dangerous_pts(
             input
-                .into_iter()
+                .iter()
+                .copied()
                 .filter(|l| l.is_horiz() || l.is_vertical()),
         )
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        dangerous_pts(input)
+    fn part2(input: &Self::Input) -> Self::Output {
+        dangerous_pts(input.iter().copied())
     }
 }
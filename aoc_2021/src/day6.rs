@@ -1,10 +1,10 @@
 use std::convert::identity;
 
 use itertools::Itertools;
-use log::debug;
 
 use aoc_lib::*;
 day!(Day6, 6);
+register!(Day6);
 
 #[derive(Clone)]
 pub struct Colony([usize; 9], usize);
@@ -39,28 +39,36 @@ impl Solver for Day6 {
     type Output = usize;
     type Input = Colony;
 
-    fn parse(input: &str) -> Self::Input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>
     where
         Self: Sized,
     {
         let counts = input
             .trim()
             .split(',')
-            .map(|s| s.trim().parse::<u8>().unwrap())
+            .map(|s| {
+                let s = s.trim();
+                parse_at::<u8>(input, s).and_then(|age| match age {
+                    0..=8 => Ok(age),
+                    _ => Err(ParseError::at(input, s, "age must be in 0..=8")),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .counts_by(identity);
         let mut freq_table = [0_usize; 9];
         for (age, count) in counts {
             freq_table[age as usize] = count;
         }
-        debug!("freq: {:?}", freq_table);
-        Colony(freq_table, 0)
+        trace!("ages", counts = format!("{:?}", freq_table));
+        Ok(Colony(freq_table, 0))
     }
 
-    fn part1(mut input: Self::Input) -> Self::Output {
-        input.simulate(80)
+    fn part1(input: &Self::Input) -> Self::Output {
+        input.clone().simulate(80)
     }
 
-    fn part2(mut input: Self::Input) -> Self::Output {
-        input.simulate(256)
+    fn part2(input: &Self::Input) -> Self::Output {
+        input.clone().simulate(256)
     }
 }
@@ -2,6 +2,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day7, 7);
+register!(Day7);
 
 fn fuel_cost(max: u32) -> Vec<u32> {
     (0..=max)
@@ -13,13 +14,16 @@ fn fuel_cost(max: u32) -> Vec<u32> {
         .collect()
 }
 
-fn solve(crabs: Vec<u32>, fuel_cost: impl Fn(u32) -> u32) -> u32 {
+fn solve(crabs: &[u32], fuel_cost: impl Fn(u32) -> u32) -> u32 {
+    let (&min, &max) = crabs.iter().minmax().into_option().unwrap_or((&0, &0));
     crabs
         .iter()
         .counts_by(std::convert::identity)
         .into_iter()
         .sorted_by(|(_, freq1), (_, freq2)| freq1.cmp(freq2).reverse())
         .map(|(pos, _)| *pos as usize)
+        // the cheapest position isn't always one a crab is at, e.g. when the cost grows with the distance
+        .chain(min as usize..=max as usize)
         // compute the fuel cost for each position, remembering the minimum we've seen
         //   starting from the most frequent position, since that's the most likely minimum cost
         //   short-circuiting when the fuel cost sum goes above the known minimum one
@@ -41,7 +45,7 @@ impl Solver for Day7 {
     type Output = u32;
     type Input = Vec<u32>;
 
-    fn parse(input: &str) -> Self::Input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>
     where
         Self: Sized,
     {
@@ -49,16 +53,16 @@ impl Solver for Day7 {
             .trim()
             .split(',')
             .filter(|l| !l.is_empty())
-            .map(|l| l.parse::<u32>().unwrap())
+            .map(|l| parse_at(input, l))
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         solve(input, std::convert::identity)
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
-        let max = input.clone().into_iter().max().unwrap() as usize;
+    fn part2(input: &Self::Input) -> Self::Output {
+        let max = *input.iter().max().unwrap() as usize;
         let fuel_cost = fuel_cost(max as u32);
         solve(input, |dist| fuel_cost[dist as usize])
     }
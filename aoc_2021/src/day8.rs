@@ -1,5 +1,6 @@
 use aoc_lib::*;
 day!(Day8, 8);
+register!(Day8, Main, Permutations);
 
 use bitflags::bitflags;
 use itertools::Itertools;
@@ -52,22 +53,25 @@ bitflags! {
 }
 
 impl Signal {
-    fn from_str(s: &str) -> Self {
-        s.chars()
-            .map(Signal::from_char)
-            .fold(Signal::empty(), |s, c| s | c)
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        s.char_indices()
+            .try_fold(Signal::empty(), |signal, (idx, ch)| {
+                Signal::from_char(ch).map(|c| signal | c).ok_or_else(|| {
+                    ParseError::at(input, &s[idx..idx + ch.len_utf8()], "invalid segment")
+                })
+            })
     }
 
-    fn from_char(ch: char) -> Self {
+    fn from_char(ch: char) -> Option<Self> {
         match ch {
-            'a' => Signal::A,
-            'b' => Signal::B,
-            'c' => Signal::C,
-            'd' => Signal::D,
-            'e' => Signal::E,
-            'f' => Signal::F,
-            'g' => Signal::G,
-            _ => panic!("invalid segment"),
+            'a' => Some(Signal::A),
+            'b' => Some(Signal::B),
+            'c' => Some(Signal::C),
+            'd' => Some(Signal::D),
+            'e' => Some(Signal::E),
+            'f' => Some(Signal::F),
+            'g' => Some(Signal::G),
+            _ => None,
         }
     }
 
@@ -157,16 +161,19 @@ impl Decoder {
         // 5 is now !n2 && !n3
         let n5 = panel.filter_from_num(3, |s| *s != n2 && *s != n3);
 
-        log::debug!("n0 = {:?}", n0);
-        log::debug!("n1 = {:?}", n1);
-        log::debug!("n2 = {:?}", n2);
-        log::debug!("n3 = {:?}", n3);
-        log::debug!("n4 = {:?}", n4);
-        log::debug!("n5 = {:?}", n5);
-        log::debug!("n6 = {:?}", n6);
-        log::debug!("n7 = {:?}", n7);
-        log::debug!("n8 = {:?}", n8);
-        log::debug!("n9 = {:?}", n9);
+        trace!(
+            "deduced",
+            n0 = format!("{:?}", n0),
+            n1 = format!("{:?}", n1),
+            n2 = format!("{:?}", n2),
+            n3 = format!("{:?}", n3),
+            n4 = format!("{:?}", n4),
+            n5 = format!("{:?}", n5),
+            n6 = format!("{:?}", n6),
+            n7 = format!("{:?}", n7),
+            n8 = format!("{:?}", n8),
+            n9 = format!("{:?}", n9),
+        );
 
         Self([n0, n1, n2, n3, n4, n5, n6, n7, n8, n9])
     }
@@ -180,29 +187,35 @@ impl Solver for Day8 {
     type Output = u32;
     type Input = Vec<Reading>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
             .map(|l| {
-                let (s, out) = l.split_once(" | ").unwrap();
+                let (s, out) = split_once_at(input, l, " | ")?;
 
-                let signals = s.split_ascii_whitespace().map(Signal::from_str).collect();
+                let signals = s
+                    .split_ascii_whitespace()
+                    .map(|s| Signal::parse(input, s))
+                    .collect::<Result<_, _>>()?;
 
-                let outputs = out.split_ascii_whitespace().map(Signal::from_str).collect();
+                let outputs = out
+                    .split_ascii_whitespace()
+                    .map(|s| Signal::parse(input, s))
+                    .collect::<Result<_, _>>()?;
 
-                Reading(Panel(signals), outputs)
+                Ok(Reading(Panel(signals), outputs))
             })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let uniques = [2, 3, 4, 7];
         input
-            .into_iter()
+            .iter()
             .map(|reading| {
                 reading
                     .1
-                    .into_iter()
+                    .iter()
                     .map(|s| s.n_bits())
                     .filter(|n| uniques.contains(n))
                     .count() as u32
@@ -210,15 +223,15 @@ impl Solver for Day8 {
             .sum::<u32>()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input
-            .into_iter()
+            .iter()
             .map(|reading| {
-                let decoder = Decoder::new(reading.0);
+                let decoder = Decoder::new(reading.0.clone());
                 reading
                     .1
-                    .into_iter()
-                    .map(|s| decoder.decode(s))
+                    .iter()
+                    .map(|s| decoder.decode(*s))
                     .join("")
                     .parse::<u32>()
                     .unwrap()
@@ -251,7 +264,7 @@ fn apply_permutation(permutation: &[char], signal: &str) -> Option<usize> {
 }
 
 /// finds a permutation of the wires that produces all 10 digits
-fn find_permutation(panel: Vec<String>) -> Vec<char> {
+fn find_permutation(panel: &[String]) -> Vec<char> {
     assert!(panel.len() == 10);
     for permutation in ('a'..='g').permutations(7) {
         let nums = panel
@@ -279,11 +292,11 @@ impl Solver<Permutations> for Day8 {
     type Output = usize;
     type Input = Vec<Line>;
 
-    fn parse(input: &str) -> Self::Input {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
         input
             .lines()
             .map(|l| {
-                let (s, out) = l.split_once(" | ").unwrap();
+                let (s, out) = split_once_at(input, l, " | ")?;
 
                 let signals = s
                     .split_ascii_whitespace()
@@ -295,18 +308,18 @@ impl Solver<Permutations> for Day8 {
                     .map(|s| s.to_owned())
                     .collect_vec();
 
-                Line { signals, outputs }
+                Ok(Line { signals, outputs })
             })
             .collect()
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         let uniques = [2, 3, 4, 7];
         input
-            .into_iter()
+            .iter()
             .map(|line| {
                 line.outputs
-                    .into_iter()
+                    .iter()
                     .map(|s| s.len())
                     .filter(|n| uniques.contains(n))
                     .count()
@@ -314,14 +327,14 @@ impl Solver<Permutations> for Day8 {
             .sum::<usize>()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input
-            .into_iter()
+            .iter()
             .map(|line| {
-                let permutation = find_permutation(line.signals);
+                let permutation = find_permutation(&line.signals);
                 line.outputs
-                    .into_iter()
-                    .map(move |output| apply_permutation(&permutation, &output).unwrap())
+                    .iter()
+                    .map(move |output| apply_permutation(&permutation, output).unwrap())
                     .join("")
                     .parse::<usize>()
                     .unwrap()
@@ -339,21 +352,22 @@ mod test {
     #[test]
     fn test_decoder_program() {
         let s = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
-        let signals = s.split_ascii_whitespace().map(Signal::from_str).collect();
+        let signal = |s| Signal::parse(s, s).unwrap();
+        let signals = s.split_ascii_whitespace().map(signal).collect();
 
         let decoder = Decoder::new(Panel(signals));
-        assert_eq!(decoder.decode(Signal::from_str("cdfeb")), 5);
-        assert_eq!(decoder.decode(Signal::from_str("fcadb")), 3);
-        assert_eq!(decoder.decode(Signal::from_str("cdfeb")), 5);
-        assert_eq!(decoder.decode(Signal::from_str("cdbaf")), 3);
+        assert_eq!(decoder.decode(signal("cdfeb")), 5);
+        assert_eq!(decoder.decode(signal("fcadb")), 3);
+        assert_eq!(decoder.decode(signal("cdfeb")), 5);
+        assert_eq!(decoder.decode(signal("cdbaf")), 3);
     }
 
     #[test]
     fn test_permutation() {
         let s = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
-        let signals = s.split_ascii_whitespace().map(|s| s.to_owned()).collect();
+        let signals = s.split_ascii_whitespace().map(|s| s.to_owned()).collect::<Vec<_>>();
 
-        let perm = find_permutation(signals);
+        let perm = find_permutation(&signals);
 
         assert_eq!(apply_permutation(&perm, "cdfeb"), Some(5));
         assert_eq!(apply_permutation(&perm, "fcadb"), Some(3));
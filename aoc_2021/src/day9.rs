@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 use aoc_lib::*;
 day!(Day9, 9);
+register!(Day9);
 
 sample!(
     Day9,
@@ -46,9 +47,11 @@ impl Grid {
 
     fn basins(&self) -> impl Iterator<Item = Vec<Pt>> + '_ {
         let mut visited = HashSet::new();
-        self.lows()
-            .map(move |pt| self.rec_neighs(pt, &mut visited))
-            .inspect(|b| log::debug!("{:?}", b))
+        self.lows().map(move |pt| {
+            let basin = self.rec_neighs(pt, &mut visited);
+            trace!("basin", x = pt.x, y = pt.y, size = basin.len());
+            basin
+        })
     }
 }
 
@@ -57,24 +60,29 @@ impl Solver for Day9 {
 
     type Input = Grid;
 
-    fn parse(input: &str) -> Self::Input {
-        Grid::from_row_iter(input.lines().filter(|l| !l.trim().is_empty()).map(|l| {
-            l.trim()
-                .chars()
-                .map(|c| {
-                    c.to_string()
-                        .parse::<u32>()
-                        .unwrap_or_else(|_| panic!("invalid line {}", l))
-                })
-                .collect_vec()
-        }))
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rows = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let l = l.trim();
+                l.char_indices()
+                    .map(|(idx, c)| {
+                        c.to_digit(10).ok_or_else(|| {
+                            ParseError::at(input, &l[idx..idx + c.len_utf8()], "not a digit")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Grid::from_row_iter(rows.into_iter()))
     }
 
-    fn part1(input: Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Self::Output {
         input.lows().map(|low| input[low] + 1).sum::<u32>()
     }
 
-    fn part2(input: Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Self::Output {
         input
             .basins()
             .map(|basin| basin.len() as u32)
@@ -347,6 +347,20 @@ impl<T> Grid<T> {
         self.values.chunks(self.w)
     }
 
+    /// Renders one line per row, e.g. for a visualization `Frame`.
+    pub fn render(&self, mut cell: impl FnMut(Pt<usize>, &T) -> char) -> String {
+        // e.g. a grid made from no points at all
+        if self.w == 0 {
+            return String::new();
+        }
+        let mut out = String::with_capacity((self.w + 1) * self.height());
+        for (y, row) in self.rows_iter().enumerate() {
+            out.extend(row.iter().enumerate().map(|(x, v)| cell(Pt::new(x, y), v)));
+            out.push('\n');
+        }
+        out
+    }
+
     pub fn neighbours_mut<N>(&mut self, coord: Pt<N>, mut f: impl FnMut(&mut T))
     where
         N: Integer + Copy + FromPrimitive + ToPrimitive + Unsigned + core::fmt::Debug,
@@ -359,6 +373,23 @@ impl<T> Grid<T> {
     }
 }
 
+impl Grid<bool> {
+    /// A grid just big enough to hold all the points, which are set.
+    pub fn from_pts<N>(pts: impl IntoIterator<Item = Pt<N>>) -> Self
+    where
+        N: ToPrimitive + Unsigned + core::fmt::Debug,
+    {
+        let pts = pts.into_iter().map(|pt| pt.to_usize()).collect_vec();
+        let w = pts.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+        let h = pts.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+        let mut grid = Self::from_iter(w, vec![false; w * h]);
+        for (x, y) in pts {
+            grid.values[y * w + x] = true;
+        }
+        grid
+    }
+}
+
 impl<T, N> Index<Pt<N>> for Grid<T>
 where
     N: ToPrimitive + Unsigned + core::fmt::Debug,
@@ -572,6 +603,25 @@ mod test {
         assert_eq!(grid.width(), 3);
         assert_eq!(grid.rows_iter().collect_vec(), rows);
     }
+
+    #[test]
+    fn test_grid_render() {
+        let grid = Grid::from_iter(3, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            grid.render(|pt, v| if pt.x == 1 { '#' } else { (b'0' + v) as char }),
+            "1#3\n4#6\n"
+        );
+
+        let grid = Grid::from_pts([Pt::new(0_u8, 0), Pt::new(2, 1)]);
+        assert_eq!(
+            grid.render(|_, set| if *set { '#' } else { '.' }),
+            "#..\n..#\n"
+        );
+
+        let grid = Grid::from_pts(Vec::<Pt<u8>>::new());
+        assert_eq!(grid.render(|_, _| '#'), "");
+    }
+
     #[test]
     fn test_grid_pts() {
         let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
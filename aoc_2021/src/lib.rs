@@ -1,33 +1,66 @@
 #![feature(drain_filter)]
 #![feature(const_fn_trait_bound)]
 #![feature(box_patterns)]
-#![feature(int_roundings)] // for div_floor
+#![feature(int_roundings)] // for div_floor and div_ceil
 
 pub(crate) mod grid;
+pub(crate) mod ocr;
 pub(crate) mod tools;
 
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+pub const YEAR: u16 = 2021;
+
+// declares the day modules and a registry with all their solvers
+macro_rules! days {
+    ($($day: ident),+ $(,)?) => {
+        $(pub mod $day;)+
+
+        pub fn registry() -> aoc_lib::Registry {
+            let mut registry = aoc_lib::Registry::new();
+            $($day::register(&mut registry);)+
+            registry
+        }
+
+        // a test per day solving its samples with every alternate, and its real input when the answers are known,
+        // inputs are read from the directory in AOC_INPUTS, inputs/ by default, they're best checked with --release
+        #[cfg(test)]
+        mod check {
+            fn inputs() -> std::path::PathBuf {
+                std::env::var_os("AOC_INPUTS")
+                    .map(Into::into)
+                    .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs").into())
+            }
+
+            $(
+                #[test]
+                fn $day() {
+                    let mut registry = aoc_lib::Registry::new();
+                    super::$day::register(&mut registry);
+                    let mismatches = registry.check(Some(&inputs()));
+                    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+                }
+            )+
+        }
+    };
+}
+
+days!(
+    day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14, day15,
+    day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+        assert_eq!(registry.years(), vec![YEAR]);
+        assert_eq!(registry.days(YEAR), (1..=25).collect::<Vec<u8>>());
+        let alts = |day| registry.day(YEAR, day).map(|e| e.alt).collect::<Vec<_>>();
+        assert_eq!(alts(1), vec!["Main"]);
+        assert_eq!(alts(15), vec!["Main", "AStar", "Dijkstra"]);
+        assert_eq!(alts(16), vec!["Main", "Bitter"]);
+        assert_eq!(alts(24), vec!["Main", "Direct"]);
+    }
+}
@@ -0,0 +1,219 @@
+//! Reads the block letters some puzzles draw their answer with.
+use itertools::Itertools;
+
+use crate::grid::{Grid, Pt};
+
+/// A font of block letters, each drawn in a `width` x `height` box, with `# ` for set and unset pixels.
+struct Font {
+    width: usize,
+    height: usize,
+    // letters are drawn one after the other, each box followed by spacing
+    stride: usize,
+    glyphs: &'static [(char, &'static str)],
+}
+
+// the usual font, e.g. 2016 day 8, 2019 days 8 and 11, 2021 day 13
+const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    stride: 5,
+    glyphs: &[
+        ('A', " ## #  ##  ######  ##  #"),
+        ('B', "### #  #### #  ##  #### "),
+        ('C', " ## #  ##   #   #  # ## "),
+        ('E', "#####   ### #   #   ####"),
+        ('F', "#####   ### #   #   #   "),
+        ('G', " ## #  ##   # ###  # ###"),
+        ('H', "#  ##  ######  ##  ##  #"),
+        ('I', " ###  #   #   #   #  ###"),
+        ('J', "  ##   #   #   ##  # ## "),
+        ('K', "#  ## # ##  # # # # #  #"),
+        ('L', "#   #   #   #   #   ####"),
+        ('O', " ## #  ##  ##  ##  # ## "),
+        ('P', "### #  ##  #### #   #   "),
+        ('R', "### #  ##  #### # # #  #"),
+        ('S', " ####   #    ##    #### "),
+        ('U', "#  ##  ##  ##  ##  # ## "),
+        ('Z', "####   #  #  #  #   ####"),
+    ],
+};
+
+// the tall font, e.g. 2018 day 10
+const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    stride: 8,
+    glyphs: &[
+        (
+            'A',
+            "  ##   #  # #    ##    ##    ########    ##    ##    ##    #",
+        ),
+        (
+            'B',
+            "##### #    ##    ##    ###### #    ##    ##    ##    ###### ",
+        ),
+        (
+            'C',
+            " #### #    ##     #     #     #     #     #     #    # #### ",
+        ),
+        (
+            'E',
+            "#######     #     #     ##### #     #     #     #     ######",
+        ),
+        (
+            'F',
+            "#######     #     #     ##### #     #     #     #     #     ",
+        ),
+        (
+            'G',
+            " #### #    ##     #     #     #  ####    ##    ##   ## ### #",
+        ),
+        (
+            'H',
+            "#    ##    ##    ##    ########    ##    ##    ##    ##    #",
+        ),
+        (
+            'J',
+            "   ###    #     #     #     #     #     # #   # #   #  ###  ",
+        ),
+        (
+            'K',
+            "#    ##   # #  #  # #   ##    ##    # #   #  #  #   # #    #",
+        ),
+        (
+            'L',
+            "#     #     #     #     #     #     #     #     #     ######",
+        ),
+        (
+            'N',
+            "#    ###   ###   ## #  ## #  ##  # ##  # ##   ###   ###    #",
+        ),
+        (
+            'P',
+            "##### #    ##    ##    ###### #     #     #     #     #     ",
+        ),
+        (
+            'R',
+            "##### #    ##    ##    ###### #  #  #   # #   # #    ##    #",
+        ),
+        (
+            'X',
+            "#    ##    # #  #  #  #   ##    ##   #  #  #  # #    ##    #",
+        ),
+        (
+            'Z',
+            "######     #     #    #    #    #    #    #     #     ######",
+        ),
+    ],
+};
+
+impl Font {
+    fn read(&self, grid: &Grid<bool>) -> Option<String> {
+        let letters = grid.width().div_ceil(self.stride);
+        (0..letters)
+            .map(|letter| {
+                let pixels = (0..self.height)
+                    .cartesian_product(0..self.width)
+                    .map(|(y, x)| {
+                        let pt = Pt::new(letter * self.stride + x, y);
+                        match grid.get(pt) {
+                            Some(true) => '#',
+                            _ => ' ',
+                        }
+                    })
+                    .collect::<String>();
+                self.glyphs
+                    .iter()
+                    .find(|(_, glyph)| *glyph == pixels)
+                    .map(|(c, _)| *c)
+            })
+            .collect()
+    }
+}
+
+/// The text drawn in a grid, letters start at the left edge, `None` if a letter isn't known.
+pub fn read(grid: &Grid<bool>) -> Option<String> {
+    if grid.width() == 0 {
+        return None;
+    }
+    match grid.height() {
+        0..=6 => SMALL.read(grid),
+        7..=10 => LARGE.read(grid),
+        _ => None,
+    }
+}
+
+/// The text drawn by a set of dots, see `read`.
+pub fn read_dots<N>(dots: impl IntoIterator<Item = Pt<N>>) -> Option<String>
+where
+    N: num::ToPrimitive + num::Unsigned + core::fmt::Debug,
+{
+    read(&Grid::from_pts(dots))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid(art: &str) -> Grid<bool> {
+        Grid::from_row_iter(art.lines().map(|l| l.chars().map(|c| c == '#').collect()))
+    }
+
+    #[test]
+    fn test_read_small() {
+        let art = "\
+###  #  # #### #### ###   ##  #  #  ##
+#  # #  #    # #    #  # #  # #  # #  #
+#  # ####   #  ###  ###  #    #  # #  #
+###  #  #  #   #    #  # #    #  # ####
+#    #  # #    #    #  # #  # #  # #  #
+#    #  # #### #### ###   ##   ##  #  #";
+        let art = art
+            .lines()
+            .map(|l| format!("{:40}", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(read(&grid(&art)), Some("PHZEBCUA".to_string()));
+        assert_eq!(read(&grid("##\n##")), None);
+        assert_eq!(read(&Grid::from_pts(Vec::<Pt<u8>>::new())), None);
+    }
+
+    #[test]
+    fn test_read_large() {
+        let art = "\
+#    #  ######
+#    #  #
+ #  #   #
+ #  #   #
+  ##    #####
+  ##    #
+ #  #   #
+ #  #   #
+#    #  #
+#    #  ######";
+        let art = art
+            .lines()
+            .map(|l| format!("{:14}", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(read(&grid(&art)), Some("XE".to_string()));
+    }
+
+    #[test]
+    fn test_read_dots() {
+        // the letter L
+        let dots = (0..6_u8)
+            .map(|y| Pt::new(0, y))
+            .chain((1..4).map(|x| Pt::new(x, 5)));
+        assert_eq!(read_dots(dots), Some("L".to_string()));
+    }
+
+    #[test]
+    fn test_fonts() {
+        for font in [SMALL, LARGE] {
+            for (c, glyph) in font.glyphs {
+                assert_eq!(glyph.len(), font.width * font.height, "{}", c);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+/// The answer to a part, compared to the expected one after normalizing both, see `matches`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    /// A single line of text, e.g. letters read from block letters
+    Text(String),
+    /// Lines of text drawing something, e.g. the block letters themselves
+    Art(String),
+    /// The part doesn't have an answer, e.g. day 25 only has one part
+    None,
+}
+
+impl Answer {
+    /// Whether this is the expected answer: numbers are compared by value, text without the surrounding whitespace,
+    /// and art without trailing whitespace or blank lines around it. Nothing matches an answer that doesn't exist.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Number(n) => expected.trim().parse::<i128>() == Ok(*n),
+            Answer::Text(text) => text == expected.trim(),
+            Answer::Art(art) => *art == lines(expected),
+            Answer::None => false,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Answer::None)
+    }
+}
+
+// the lines of some text without trailing whitespace, nor blank lines at the start or at the end
+fn lines(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .skip_while(|l| l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(text) | Answer::Art(text) => write!(f, "{}", text),
+            Answer::None => write!(f, "no answer"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t: ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// Text is art when it has more than one line.
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        let text = lines(&text);
+        if text.contains('\n') {
+            Answer::Art(text)
+        } else {
+            Answer::Text(text.trim_start().to_string())
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::from(text.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::None, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(37_usize), Answer::Number(37));
+        assert_eq!(Answer::from(-3_i32), Answer::Number(-3));
+        assert_eq!(
+            Answer::from(" EFLFJGRF\n"),
+            Answer::Text("EFLFJGRF".to_string())
+        );
+        assert_eq!(
+            Answer::from("\n#..#  \n####\n\n".to_string()),
+            Answer::Art("#..#\n####".to_string())
+        );
+        assert_eq!(Answer::from(None::<u32>), Answer::None);
+        assert_eq!(Answer::from(Some(3_u32)), Answer::Number(3));
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(Answer::Number(37).matches("37\n"));
+        assert!(Answer::Number(37).matches(" 037"));
+        assert!(!Answer::Number(37).matches("38"));
+        assert!(!Answer::Number(37).matches("thirty-seven"));
+        assert!(Answer::Text("abc".to_string()).matches("abc\n"));
+        assert!(!Answer::Text("abc".to_string()).matches("ABC"));
+        assert!(Answer::Art("#.\n.#".to_string()).matches("\n#. \n.#\n"));
+        assert!(!Answer::Art("#.\n.#".to_string()).matches("#.\n#."));
+        assert!(!Answer::None.matches(""));
+    }
+}
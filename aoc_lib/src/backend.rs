@@ -0,0 +1,203 @@
+use crate::{Input, Part};
+use aocf::{Aoc, Level};
+
+/// Where puzzle inputs come from and answers go to.
+pub trait Backend {
+    fn input(&mut self, year: u16, day: u8) -> anyhow::Result<String>;
+
+    /// The answer accepted for a part, if any.
+    fn answer(&mut self, year: u16, day: u8, part: Part) -> anyhow::Result<Option<String>>;
+
+    /// Submits an answer, returns the text of the response.
+    fn submit(&mut self, year: u16, day: u8, part: Part, answer: &str) -> anyhow::Result<String>;
+}
+
+/// The puzzle site through aocf and its local cache.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Aocf;
+
+impl Aocf {
+    fn aoc(year: u16, day: u8) -> anyhow::Result<Aoc> {
+        // aocf errors aren't std errors
+        Aoc::new()
+            .parse_cli(false)
+            .year(Some(year as i32))
+            .day(Some(day as u32))
+            .init()
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+impl Backend for Aocf {
+    fn input(&mut self, year: u16, day: u8) -> anyhow::Result<String> {
+        Self::aoc(year, day)?
+            .get_input(false)
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    fn answer(&mut self, year: u16, day: u8, part: Part) -> anyhow::Result<Option<String>> {
+        let level = match part {
+            Part::One => Level::First,
+            Part::Two => Level::Second,
+        };
+        Ok(Self::aoc(year, day)?.solution.get(&level).cloned())
+    }
+
+    fn submit(&mut self, year: u16, day: u8, part: Part, answer: &str) -> anyhow::Result<String> {
+        let mut aoc = Self::aoc(year, day)?;
+        // aocf submits to the first unsolved part
+        if part == Part::Two && !aoc.solution.contains_key(&Level::First) {
+            anyhow::bail!("part One of day {} must be solved first", day);
+        }
+        aoc.submit(answer).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+/// A server with the same endpoints as the puzzle site, e.g. a local stand-in.
+pub struct Http {
+    url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Http {
+    pub fn new(url: impl Into<String>, session: Option<String>) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let request = self.agent.request(method, &format!("{}{}", self.url, path));
+        match &self.session {
+            Some(session) => request.set("Cookie", &format!("session={}", session)),
+            None => request,
+        }
+    }
+}
+
+impl Backend for Http {
+    fn input(&mut self, year: u16, day: u8) -> anyhow::Result<String> {
+        let path = format!("/{}/day/{}/input", year, day);
+        Ok(self.request("GET", &path).call()?.into_string()?)
+    }
+
+    fn answer(&mut self, year: u16, day: u8, part: Part) -> anyhow::Result<Option<String>> {
+        let path = format!("/{}/day/{}", year, day);
+        let page = self.request("GET", &path).call()?.into_string()?;
+        let answer = accepted_answers(&page).nth(match part {
+            Part::One => 0,
+            Part::Two => 1,
+        });
+        Ok(answer)
+    }
+
+    fn submit(&mut self, year: u16, day: u8, part: Part, answer: &str) -> anyhow::Result<String> {
+        let path = format!("/{}/day/{}/answer", year, day);
+        let level = match part {
+            Part::One => "1",
+            Part::Two => "2",
+        };
+        let page = self
+            .request("POST", &path)
+            .send_form(&[("level", level), ("answer", answer)])?
+            .into_string()?;
+        Ok(article(&page))
+    }
+}
+
+// answers are shown in order below each part of the puzzle
+fn accepted_answers(page: &str) -> impl Iterator<Item = String> + '_ {
+    page.split("Your puzzle answer was <code>")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</code>"))
+        .map(|(answer, _)| answer.to_string())
+}
+
+// the text of the article holding the response, or of the whole page if there's none
+fn article(page: &str) -> String {
+    let content = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(page);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in content.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// A day's input and accepted answers, fetched from a backend when loaded.
+pub struct RemoteInput {
+    backend: Box<dyn Backend>,
+    year: u16,
+    day: u8,
+    answers: [Option<String>; 2],
+}
+
+impl RemoteInput {
+    pub fn new(backend: Box<dyn Backend>, year: u16, day: u8) -> Self {
+        Self {
+            backend,
+            year,
+            day,
+            answers: Default::default(),
+        }
+    }
+}
+
+impl Input for RemoteInput {
+    fn load(&mut self) -> anyhow::Result<String> {
+        let input = self.backend.input(self.year, self.day)?;
+        for (idx, part) in [Part::One, Part::Two].into_iter().enumerate() {
+            self.answers[idx] = self.backend.answer(self.year, self.day, part)?;
+        }
+        Ok(input)
+    }
+
+    fn solution(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.answers[0].clone(),
+            Part::Two => self.answers[1].clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_accepted_answers() {
+        let page = "<article>...</article><p>Your puzzle answer was <code>37</code>.</p>\
+            <article>...</article><p>Your puzzle answer was <code>168</code>.</p>";
+        assert_eq!(
+            accepted_answers(page).collect::<Vec<_>>(),
+            vec!["37", "168"]
+        );
+        assert_eq!(
+            accepted_answers("<p>To play, please identify yourself</p>").count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_article() {
+        let page = "<html><main><article><p>That's not the right answer; \
+            <em>your answer is too low</em>.</p></article></main></html>";
+        assert_eq!(
+            article(page),
+            "That's not the right answer; your answer is too low."
+        );
+        assert_eq!(article(" plain "), "plain");
+    }
+}
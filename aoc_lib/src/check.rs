@@ -0,0 +1,57 @@
+use crate::{Entry, Input, LocalInput, Part, Registry};
+use std::path::Path;
+
+impl Registry {
+    /// Solves the samples of every entry, and the real inputs in `inputs` whose answers are known, e.g.
+    /// `inputs/2021/day07.txt` with `inputs/2021/day07.answers`; returns a description of each mismatch.
+    pub fn check(&self, inputs: Option<&Path>) -> Vec<String> {
+        let mut mismatches = vec![];
+        for entry in self.entries() {
+            for sample in entry.samples() {
+                mismatches.extend(check(entry, sample.name, sample.content, |part| {
+                    sample.answer(part).map(str::to_string)
+                }));
+            }
+            if let Some(dir) = inputs {
+                let mut input = LocalInput::new(dir, entry.year, entry.day);
+                // inputs that aren't there are just not checked
+                if let Ok(content) = input.load() {
+                    let name = input.path().display().to_string();
+                    mismatches.extend(check(entry, &name, &content, |part| input.solution(part)));
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+// solves the parts of an input with known answers
+fn check(
+    entry: &Entry,
+    name: &str,
+    input: &str,
+    answer: impl Fn(Part) -> Option<String>,
+) -> Vec<String> {
+    let parts = [Part::One, Part::Two]
+        .into_iter()
+        .filter_map(|part| Some((part, answer(part)?)))
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        return vec![];
+    }
+    let parsed = match entry.parse(input) {
+        Ok((parsed, _)) => parsed,
+        Err(e) => return vec![format!("{:?}, {}: {}", entry, name, e)],
+    };
+    parts
+        .into_iter()
+        .filter_map(|(part, expected)| match entry.solve_parsed(&parsed, part) {
+            Ok((answer, _)) if answer.matches(&expected) => None,
+            Ok((answer, _)) => Some(format!(
+                "{:?}, {} part {:?}: got {}, expected {}",
+                entry, name, part, answer, expected
+            )),
+            Err(e) => Some(format!("{:?}, {} part {:?}: {}", entry, name, part, e)),
+        })
+        .collect()
+}
@@ -0,0 +1,438 @@
+use crate::Part;
+use anyhow::Context;
+use std::fmt::{self, Display};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too recently, the answer wasn't checked
+    Wait,
+    /// The part was already solved, the answer wasn't checked
+    Solved,
+    Unknown,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 7] = [
+        Outcome::Correct,
+        Outcome::Wrong,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::Wait,
+        Outcome::Solved,
+        Outcome::Unknown,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wait => "wait",
+            Outcome::Solved => "solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A submitted answer and the response to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub at: SystemTime,
+    pub outcome: Outcome,
+    /// How long to wait before submitting again
+    pub wait: Option<Duration>,
+}
+
+impl Submission {
+    /// Parses the outcome and cooldown out of the text of the response.
+    pub fn new(year: u16, day: u8, part: Part, answer: &str, response: &str) -> Self {
+        let response = response.to_ascii_lowercase();
+        let outcome = if response.contains("that's the right answer") {
+            Outcome::Correct
+        } else if response.contains("you gave an answer too recently") {
+            Outcome::Wait
+        } else if response.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if response.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if response.contains("that's not the right answer") {
+            Outcome::Wrong
+        } else if response.contains("did you already complete it") {
+            Outcome::Solved
+        } else {
+            Outcome::Unknown
+        };
+        Self {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            at: SystemTime::now(),
+            outcome,
+            wait: parse_wait(&response),
+        }
+    }
+
+    // one tab separated line, the answer goes last
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            match self.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            self.at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            self.outcome,
+            self.wait.map(|wait| wait.as_secs()).unwrap_or_default(),
+            self.answer
+        )
+    }
+
+    fn from_line(line: &str) -> anyhow::Result<Self> {
+        let fields = line.splitn(7, '\t').collect::<Vec<_>>();
+        if fields.len() != 7 {
+            anyhow::bail!("expected 7 fields, got {}", fields.len());
+        }
+        let outcome = Outcome::ALL
+            .into_iter()
+            .find(|outcome| outcome.name() == fields[4])
+            .with_context(|| format!("unknown outcome {}", fields[4]))?;
+        let wait = fields[5].parse::<u64>()?;
+        Ok(Self {
+            year: fields[0].parse()?,
+            day: fields[1].parse()?,
+            part: fields[2].parse()?,
+            answer: fields[6].to_string(),
+            at: UNIX_EPOCH + Duration::from_secs(fields[3].parse()?),
+            outcome,
+            wait: (wait > 0).then(|| Duration::from_secs(wait)),
+        })
+    }
+}
+
+// e.g.: "You have 1m 5s left to wait" or "Please wait one minute before trying again"
+fn parse_wait(response: &str) -> Option<Duration> {
+    if let Some((before, _)) = response.split_once(" left to wait") {
+        if let Some((_, left)) = before.rsplit_once("you have ") {
+            return left
+                .split_whitespace()
+                .map(|amount| {
+                    let (n, unit) = amount.split_at(amount.len().checked_sub(1)?);
+                    let unit = match unit {
+                        "h" => 3600,
+                        "m" => 60,
+                        "s" => 1,
+                        _ => return None,
+                    };
+                    Some(n.parse::<u64>().ok()? * unit)
+                })
+                .sum::<Option<u64>>()
+                .map(Duration::from_secs);
+        }
+    }
+
+    let (_, rest) = response.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let n = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    let unit = match words.next()?.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(n * unit))
+}
+
+/// Why an answer shouldn't be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    Solved(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+    Cooldown(Duration),
+    /// The site takes a single line, e.g. the letters block letters spell rather than the block letters
+    MultiLine,
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong => write!(f, "already submitted and wrong"),
+            Refusal::TooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::TooLow(bound) => write!(f, "{} was already too low", bound),
+            Refusal::Cooldown(left) => write!(
+                f,
+                "submitted too recently, {}s left to wait",
+                left.as_secs()
+            ),
+            Refusal::MultiLine => write!(f, "answers can't span several lines"),
+        }
+    }
+}
+
+/// A local record of all the submitted answers, to avoid submitting answers that can't be right.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, which doesn't need to exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let submissions = match std::fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(idx, line)| {
+                    Submission::from_line(line).with_context(|| {
+                        format!("invalid submission at {}:{}", path.display(), idx + 1)
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => {
+                return Err(e).with_context(|| format!("cannot read ledger {}", path.display()))
+            }
+        };
+        Ok(Self { path, submissions })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// Whether an answer is worth submitting at `now`, given what was submitted before.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<(), Refusal> {
+        // it couldn't be recorded either, the ledger has a line per submission
+        if answer.contains('\n') {
+            return Err(Refusal::MultiLine);
+        }
+
+        let day = self
+            .submissions
+            .iter()
+            .filter(|s| s.year == year && s.day == day);
+
+        // the cooldown applies to both parts
+        let until = day.clone().filter_map(|s| Some(s.at + s.wait?)).max();
+        if let Some(left) = until.and_then(|until| until.duration_since(now).ok()) {
+            return Err(Refusal::Cooldown(left));
+        }
+
+        let part = day.filter(|s| s.part == part).collect::<Vec<_>>();
+        if let Some(correct) = part.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::Solved(correct.answer.clone()));
+        }
+        if part
+            .iter()
+            .any(|s| s.outcome.is_wrong() && s.answer == answer)
+        {
+            return Err(Refusal::KnownWrong);
+        }
+
+        // bounds only make sense for numbers
+        let n = match answer.parse::<i64>() {
+            Ok(n) => n,
+            Err(_) => return Ok(()),
+        };
+        let bound = |outcome| {
+            part.iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i64>().ok())
+        };
+        if let Some(high) = bound(Outcome::TooHigh).filter(|&high| n >= high).min() {
+            return Err(Refusal::TooHigh(high.to_string()));
+        }
+        if let Some(low) = bound(Outcome::TooLow).filter(|&low| n <= low).max() {
+            return Err(Refusal::TooLow(low.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Adds a submission to the ledger and appends it to its file.
+    pub fn record(&mut self, submission: Submission) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("cannot open ledger {}", self.path.display()))?;
+        writeln!(file, "{}", submission.to_line())?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn submission(part: Part, answer: &str, response: &str) -> Submission {
+        Submission {
+            at: UNIX_EPOCH + Duration::from_secs(1000),
+            ..Submission::new(2021, 7, part, answer, response)
+        }
+    }
+
+    #[test]
+    fn test_submission() {
+        let s = submission(
+            Part::One,
+            "37",
+            "That's the right answer! You are one gold star closer.",
+        );
+        assert_eq!((s.outcome, s.wait), (Outcome::Correct, None));
+
+        let s = submission(Part::One, "37", "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.");
+        assert_eq!(
+            (s.outcome, s.wait),
+            (Outcome::TooHigh, Some(Duration::from_secs(60)))
+        );
+
+        let s = submission(Part::Two, "37", "That's not the right answer; your answer is too low. please wait 5 minutes before trying again.");
+        assert_eq!(
+            (s.outcome, s.wait),
+            (Outcome::TooLow, Some(Duration::from_secs(300)))
+        );
+
+        let s = submission(
+            Part::Two,
+            "37",
+            "That's not the right answer. Please wait one minute before trying again.",
+        );
+        assert_eq!(s.outcome, Outcome::Wrong);
+
+        let s = submission(Part::Two, "37", "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.");
+        assert_eq!(
+            (s.outcome, s.wait),
+            (Outcome::Wait, Some(Duration::from_secs(65)))
+        );
+
+        let s = submission(
+            Part::Two,
+            "37",
+            "You don't seem to be solving the right level. Did you already complete it?",
+        );
+        assert_eq!((s.outcome, s.wait), (Outcome::Solved, None));
+
+        assert_eq!(Submission::from_line(&s.to_line()).unwrap(), s);
+    }
+
+    #[test]
+    fn test_check() {
+        let ledger = Ledger {
+            path: PathBuf::new(),
+            submissions: vec![
+                submission(Part::One, "37", "That's the right answer!"),
+                submission(
+                    Part::Two,
+                    "100",
+                    "your answer is too high. please wait 5 minutes",
+                ),
+                submission(Part::Two, "10", "your answer is too low."),
+                submission(Part::Two, "abc", "That's not the right answer."),
+            ],
+        };
+        let check = |part, answer, secs| {
+            ledger.check(
+                2021,
+                7,
+                part,
+                answer,
+                UNIX_EPOCH + Duration::from_secs(secs),
+            )
+        };
+
+        assert_eq!(
+            check(Part::Two, "50", 1100),
+            Err(Refusal::Cooldown(Duration::from_secs(200)))
+        );
+        assert_eq!(
+            check(Part::One, "38", 2000),
+            Err(Refusal::Solved("37".to_string()))
+        );
+        assert_eq!(check(Part::Two, "abc", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(check(Part::Two, "100", 2000), Err(Refusal::KnownWrong));
+        assert_eq!(
+            check(Part::Two, "120", 2000),
+            Err(Refusal::TooHigh("100".to_string()))
+        );
+        assert_eq!(
+            check(Part::Two, "3", 2000),
+            Err(Refusal::TooLow("10".to_string()))
+        );
+        assert_eq!(check(Part::Two, "50", 2000), Ok(()));
+        assert_eq!(check(Part::Two, "def", 2000), Ok(()));
+        assert_eq!(check(Part::Two, "#.\n.#", 2000), Err(Refusal::MultiLine));
+        assert_eq!(ledger.check(2021, 8, Part::One, "37", UNIX_EPOCH), Ok(()));
+    }
+
+    #[test]
+    fn test_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.tsv");
+
+        let mut ledger = Ledger::open(&path).unwrap();
+        assert!(ledger.submissions().is_empty());
+        let s = submission(Part::Two, "1 2", "That's not the right answer.");
+        ledger.record(s.clone()).unwrap();
+        ledger.record(s.clone()).unwrap();
+
+        assert_eq!(Ledger::open(&path).unwrap().submissions(), &[s.clone(), s]);
+
+        std::fs::write(&path, "2021\t7\n").unwrap();
+        assert!(Ledger::open(&path).is_err());
+    }
+}
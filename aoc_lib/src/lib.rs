@@ -1,7 +1,23 @@
 #![feature(const_fn_trait_bound)]
-use aocf::{Aoc, Level};
+#![feature(thread_local)] // for recording traces and metrics per thread
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy)]
+mod answer;
+mod backend;
+mod check;
+mod ledger;
+mod local;
+pub mod metrics;
+pub mod puzzle;
+pub mod trace;
+pub use answer::Answer;
+pub use backend::{Aocf, Backend, Http, RemoteInput};
+pub use ledger::{Ledger, Outcome, Refusal, Submission};
+pub use local::{LocalInput, StdinInput};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Part {
     One,
     Two,
@@ -20,49 +36,98 @@ impl std::str::FromStr for Part {
 }
 
 pub trait Day {
+    const YEAR: u16;
     const DAY: u8;
 }
 
+/// Declares a day, its year is the `YEAR` constant of the calling crate unless given explicitly.
+// `crate` is meant to resolve in the crate declaring the day, not in this one
+#[allow(clippy::crate_in_macro_def)]
 #[macro_export]
 macro_rules! day {
     ($d: ident, $n: literal) => {
+        day!($d, crate::YEAR, $n);
+    };
+    ($d: ident, $year: expr, $n: literal) => {
         #[derive(Clone, Copy)]
         pub struct $d;
         impl Day for $d {
+            const YEAR: u16 = $year;
             const DAY: u8 = $n;
         }
     };
 }
 
 pub trait Input {
-    fn load(&mut self) -> String; // TODO: how do we make the Aoc implementation require &mut only?
+    fn load(&mut self) -> anyhow::Result<String>;
     fn solution(&self, part: Part) -> Option<String>;
 }
 
-pub trait Sample {
-    const CONTENT: &'static str;
-    const PART1: &'static str;
-    const PART2: Option<&'static str>;
+/// A sample input, usually from the puzzle description, with the answers it should give.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub name: &'static str,
+    pub content: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
 }
 
-impl<S> Input for S
-where
-    S: Sample,
-{
-    fn load(&mut self) -> String {
-        <S as Sample>::CONTENT.to_string()
+impl Example {
+    /// A sample without any answer, see `part1` and `part2`.
+    pub const fn new(name: &'static str, content: &'static str) -> Self {
+        Self {
+            name,
+            content,
+            part1: None,
+            part2: None,
+        }
     }
 
-    fn solution(&self, part: Part) -> Option<String> {
+    pub const fn part1(self, answer: &'static str) -> Self {
+        Self {
+            part1: Some(answer),
+            ..self
+        }
+    }
+
+    pub const fn part2(self, answer: &'static str) -> Self {
+        Self {
+            part2: Some(answer),
+            ..self
+        }
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
         match part {
-            Part::One => Some(<S as Sample>::PART1.to_string()),
-            Part::Two => <S as Sample>::PART2.map(|s| s.to_string()),
+            Part::One => self.part1,
+            Part::Two => self.part2,
         }
     }
 }
 
+impl Input for Example {
+    fn load(&mut self) -> anyhow::Result<String> {
+        Ok(self.content.to_string())
+    }
+
+    fn solution(&self, part: Part) -> Option<String> {
+        self.answer(part).map(|s| s.to_string())
+    }
+}
+
+pub trait Sample {
+    const SAMPLES: &'static [Example];
+}
+
+/// Declares the samples of a day, either a single one with its answers or any number of `Example`s, e.g.:
+/// `sample!(Day16, Example::new("literal", "D2FE28").part1("6"), Example::new("sum", "C200B40A82").part2("3"))`
 #[macro_export]
 macro_rules! sample {
+    ($day: path) => {
+        impl ::aoc_lib::Sample for $day {
+            const SAMPLES: &'static [::aoc_lib::Example] = &[];
+        }
+    };
     ($day: path, $content: literal, $part1: literal, $part2: literal) => {
         sample!($day, $content, $part1, Some($part2));
     };
@@ -70,36 +135,178 @@ macro_rules! sample {
         sample!($day, $content, $part1, None);
     };
     ($day: path, $content: literal, $part1: literal, $part2: expr) => {
+        sample!(
+            $day,
+            ::aoc_lib::Example {
+                name: "sample",
+                content: $content,
+                part1: Some($part1),
+                part2: $part2,
+            }
+        );
+    };
+    ($day: path, $($example: expr),+ $(,)?) => {
         impl ::aoc_lib::Sample for $day {
-            const CONTENT: &'static str = $content;
-            const PART1: &'static str = $part1;
-            const PART2: Option<&'static str> = $part2;
+            const SAMPLES: &'static [::aoc_lib::Example] = &[$($example),+];
         }
     };
 }
 
-impl Input for Aoc {
-    fn load(&mut self) -> String {
-        self.get_input(false).unwrap()
+/// An error raised while parsing a puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The puzzle day, filled in by `solve_part` since solvers don't need to know about it.
+    pub day: Option<u8>,
+    /// 1-based line number, or 0 if the location is unknown
+    pub line: usize,
+    /// 1-based column number (in chars), or 0 if the location is unknown
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, reason: impl ToString) -> Self {
+        Self {
+            day: None,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
     }
 
-    fn solution(&self, part: Part) -> Option<String> {
-        match part {
-            Part::One => self.solution.get(&Level::First).cloned(),
-            Part::Two => self.solution.get(&Level::Second).cloned(),
+    /// Builds an error for `at`, which must be a slice of `input` for the line and column to be found.
+    pub fn at(input: &str, at: &str, reason: impl ToString) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + at.len() <= input.len());
+
+        match offset {
+            Some(offset) => {
+                let before = &input[..offset];
+                let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+                let line = before.matches('\n').count() + 1;
+                let column = before[line_start..].chars().count() + 1;
+                Self::new(line, column, at, reason)
+            }
+            None => Self::new(0, 0, at, reason),
         }
     }
+
+    pub fn for_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        // only show the first line of the offending text, it can be the whole input
+        let mut lines = self.text.lines();
+        let text = lines.next().unwrap_or_default();
+        let ellipsis = if lines.next().is_some() { "..." } else { "" };
+        write!(
+            f,
+            "line {}, column {}: {} (at `{}{}`)",
+            self.line, self.column, self.reason, text, ellipsis
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `s` with `FromStr`, reporting failures at the location of `s` within `input`.
+pub fn parse_at<T>(input: &str, s: &str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    s.parse::<T>().map_err(|e| ParseError::at(input, s, e))
+}
+
+/// Like `str::split_once`, but reports a missing delimiter at the location of `s` within `input`.
+pub fn split_once_at<'a>(
+    input: &str,
+    s: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, s, format!("expected `{}`", delimiter)))
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    Solve { day: u8, part: Part, reason: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "cannot parse input: {}", e),
+            Error::Solve { day, part, reason } => {
+                write!(f, "day {}, part {:?} failed: {}", day, part, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
 }
 
 #[derive(Debug)]
 pub struct Main;
 
+/// A step of a visualization, see `Solver::visualize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// What happened in this step, e.g. `step 12: 35 flashes`
+    pub caption: String,
+    /// What to draw, one line per row
+    pub content: String,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            caption: caption.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Frames are produced lazily, simulations can be long.
+pub type Frames<'a> = Box<dyn Iterator<Item = Frame> + 'a>;
+
+/// Parts only borrow the input so that it's parsed once for both parts, solvers that mutate it work on a clone.
+/// Alternates can share it too, see `SHARES_INPUT`.
 pub trait Solver<Alt = Main> {
-    type Output: ToString;
+    type Output: Into<Answer>;
     type Input: Sized;
-    fn parse(input: &str) -> Self::Input;
-    fn part1(input: Self::Input) -> Self::Output;
-    fn part2(input: Self::Input) -> Self::Output;
+    /// The parts the puzzle has, the others are never solved and have no answer, e.g. day 25 only has part one.
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+    /// Whether this solver parses inputs exactly like the solvers of its day that set it too, an input is then parsed
+    /// once for all of them. Set it on solvers that reuse the same parse, having the same `Input` type isn't enough.
+    const SHARES_INPUT: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output;
+    fn part2(input: &Self::Input) -> Self::Output;
+
+    /// Frames showing how a part gets solved, for solvers that simulate something worth watching.
+    fn visualize(_input: &Self::Input, _part: Part) -> Option<Frames<'_>> {
+        None
+    }
 }
 
 macro_rules! timed {
@@ -111,14 +318,470 @@ macro_rules! timed {
     }};
 }
 
-pub fn solve_part<D: Day, A>(input: &str, part: Part) -> (String, std::time::Duration)
+// extracts the message out of a panic payload
+fn panic_reason(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// How long it took to parse an input and to solve a part from it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+impl std::ops::AddAssign for Timings {
+    fn add_assign(&mut self, rhs: Self) {
+        self.parse += rhs.parse;
+        self.solve += rhs.solve;
+    }
+}
+
+pub fn solve_part<D, A>(input: &str, part: Part) -> Result<(Answer, Timings), Error>
+where
+    D: Day + Solver<A>,
+{
+    let (parsed, parse) = timed!(<D as Solver<A>>::parse(input));
+    let parsed = parsed.map_err(|e| e.for_day(D::DAY))?;
+    let (solution, solve) = solve_parsed::<D, A>(&parsed, part)?;
+    Ok((solution, Timings { parse, solve }))
+}
+
+fn solve_parsed<D, A>(
+    parsed: &<D as Solver<A>>::Input,
+    part: Part,
+) -> Result<(Answer, Duration), Error>
+where
+    D: Day + Solver<A>,
+{
+    if !<D as Solver<A>>::PARTS.contains(&part) {
+        return Ok((Answer::None, Duration::ZERO));
+    }
+    // a solver that panics should only fail its own part, not the whole run
+    let (solution, duration) =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match part {
+            Part::One => timed!(<D as Solver<A>>::part1(parsed)),
+            Part::Two => timed!(<D as Solver<A>>::part2(parsed)),
+        }))
+        .map_err(|payload| Error::Solve {
+            day: D::DAY,
+            part,
+            reason: panic_reason(payload),
+        })?;
+    Ok((solution.into(), duration))
+}
+
+/// A parsed input with its type erased, see `Entry::parse`.
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+fn visualize_parsed<D, A>(parsed: &Parsed, part: Part) -> Option<Frames<'_>>
 where
     D: Solver<A>,
+    <D as Solver<A>>::Input: 'static,
 {
-    let parsed = <D as Solver<A>>::parse(input);
-    let (solution, duration) = match part {
-        Part::One => timed!(<D as Solver<A>>::part1(parsed)),
-        Part::Two => timed!(<D as Solver<A>>::part2(parsed)),
+    let parsed = parsed
+        .downcast_ref::<<D as Solver<A>>::Input>()
+        .expect("input parsed by another solver");
+    <D as Solver<A>>::visualize(parsed, part)
+}
+
+// a part's solution and how long it took
+type Solved = Result<(Answer, Duration), Error>;
+
+/// A solver registered in a `Registry`, with its day and alternate erased.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    /// The name of the alternate, e.g. `Main` or `AStar`
+    pub alt: &'static str,
+    /// The type of the parsed input, when it's shared, see `Solver::SHARES_INPUT`
+    input: Option<TypeId>,
+    parse: fn(&str) -> Result<Parsed, Error>,
+    solve: fn(&Parsed, Part) -> Solved,
+    visualize: fn(&Parsed, Part) -> Option<Frames<'_>>,
+    samples: &'static [Example],
+}
+
+impl Entry {
+    fn new<D, A>() -> Self
+    where
+        D: Day + Solver<A> + Sample + 'static,
+        <D as Solver<A>>::Input: Send + Sync + 'static,
+    {
+        let alt = std::any::type_name::<A>();
+        Self {
+            year: D::YEAR,
+            day: D::DAY,
+            alt: alt.rsplit("::").next().unwrap_or(alt),
+            input: if <D as Solver<A>>::SHARES_INPUT {
+                Some(TypeId::of::<<D as Solver<A>>::Input>())
+            } else {
+                None
+            },
+            parse: |input| {
+                let parsed = <D as Solver<A>>::parse(input).map_err(|e| e.for_day(D::DAY))?;
+                Ok(Arc::new(parsed))
+            },
+            solve: |parsed, part| {
+                let parsed = parsed
+                    .downcast_ref::<<D as Solver<A>>::Input>()
+                    .expect("input parsed by another solver");
+                solve_parsed::<D, A>(parsed, part)
+            },
+            visualize: visualize_parsed::<D, A>,
+            samples: D::SAMPLES,
+        }
+    }
+
+    /// Parses an input for this entry's `solve_parsed`, along with how long it took.
+    pub fn parse(&self, input: &str) -> Result<(Parsed, Duration), Error> {
+        let (parsed, duration) = timed!((self.parse)(input));
+        Ok((parsed?, duration))
+    }
+
+    /// Solves a part from an input returned by the `parse` of this entry, or of one it `shares_input` with.
+    pub fn solve_parsed(&self, parsed: &Parsed, part: Part) -> Result<(Answer, Duration), Error> {
+        (self.solve)(parsed, part)
+    }
+
+    /// The frames of the visualization of a part, if the solver has one.
+    pub fn visualize<'a>(&self, parsed: &'a Parsed, part: Part) -> Option<Frames<'a>> {
+        (self.visualize)(parsed, part)
+    }
+
+    /// Whether inputs parsed by `other` can be solved by this entry, both must opt in with `Solver::SHARES_INPUT`.
+    pub fn shares_input(&self, other: &Entry) -> bool {
+        self.year == other.year
+            && self.day == other.day
+            && self.input.is_some()
+            && self.input == other.input
+    }
+
+    /// Same as `solve_part`, for this entry's day and alternate.
+    pub fn solve(&self, input: &str, part: Part) -> Result<(Answer, Timings), Error> {
+        let (parsed, parse) = self.parse(input)?;
+        let (solution, solve) = self.solve_parsed(&parsed, part)?;
+        Ok((solution, Timings { parse, solve }))
+    }
+
+    pub fn samples(&self) -> &'static [Example] {
+        self.samples
+    }
+}
+
+impl std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {} {}", self.year, self.day, self.alt)
+    }
+}
+
+/// All the solvers of one or more years, with their alternates, see the `register!` macro.
+#[derive(Debug, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<D, A>(&mut self)
+    where
+        D: Day + Solver<A> + Sample + 'static,
+        <D as Solver<A>>::Input: Send + Sync + 'static,
+    {
+        self.entries.push(Entry::new::<D, A>());
+    }
+
+    /// Adds all the entries of another registry, typically the one of another year crate.
+    pub fn merge(&mut self, other: Registry) {
+        self.entries.extend(other.entries);
+    }
+
+    /// All entries, in registration order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The entries of a single day, the main solver usually comes first.
+    pub fn day(&self, year: u16, day: u8) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.year == year && entry.day == day)
+    }
+
+    /// The registered years, sorted.
+    pub fn years(&self) -> Vec<u16> {
+        let mut years = self
+            .entries
+            .iter()
+            .map(|entry| entry.year)
+            .collect::<Vec<_>>();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+
+    /// The registered days of a year, sorted.
+    pub fn days(&self, year: u16) -> Vec<u8> {
+        let mut days = self
+            .entries
+            .iter()
+            .filter(|entry| entry.year == year)
+            .map(|entry| entry.day)
+            .collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+}
+
+/// Generates a `register` function for a day's solver and its alternates, e.g.: `register!(Day15, Main, AStar)`
+#[macro_export]
+macro_rules! register {
+    ($day: ident) => {
+        register!($day, Main);
+    };
+    ($day: ident, $($alt: ty),+) => {
+        pub fn register(registry: &mut ::aoc_lib::Registry) {
+            $(registry.register::<$day, $alt>();)+
+        }
     };
-    (solution.to_string(), duration)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_at() {
+        let input = "1,2\n3;4\n5,6";
+        let line = input.lines().nth(1).unwrap();
+        let e = ParseError::at(input, &line[1..2], "expected `,`");
+        assert_eq!(e.line, 2);
+        assert_eq!(e.column, 2);
+        assert_eq!(e.text, ";");
+
+        let e = ParseError::at(input, "not in input", "oops");
+        assert_eq!((e.line, e.column), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_at() {
+        let input = "12\nab";
+        assert_eq!(parse_at::<u8>(input, &input[0..2]), Ok(12));
+        let e = parse_at::<u8>(input, &input[3..]).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "ab"));
+    }
+
+    #[test]
+    fn test_split_once_at() {
+        let input = "a-b\nc";
+        assert_eq!(split_once_at(input, &input[..3], "-"), Ok(("a", "b")));
+        let e = split_once_at(input, &input[4..], "-").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.reason, "expected `-`");
+    }
+
+    #[test]
+    fn test_timings() {
+        let mut timings = Timings {
+            parse: Duration::from_millis(1),
+            solve: Duration::from_millis(2),
+        };
+        timings += timings;
+        assert_eq!(timings.parse, Duration::from_millis(2));
+        assert_eq!(timings.total(), Duration::from_millis(6));
+    }
+
+    struct Toy;
+    impl Day for Toy {
+        const YEAR: u16 = 2021;
+        const DAY: u8 = 3;
+    }
+
+    struct Other;
+    impl Day for Other {
+        const YEAR: u16 = 2020;
+        const DAY: u8 = 3;
+    }
+    impl Sample for Other {
+        const SAMPLES: &'static [Example] = &[];
+    }
+    impl Solver for Other {
+        type Output = u32;
+        type Input = Vec<u32>;
+        const PARTS: &'static [Part] = &[Part::One];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            <Toy as Solver>::parse(input)
+        }
+
+        fn part1(input: &Self::Input) -> Self::Output {
+            <Toy as Solver>::part1(input)
+        }
+
+        fn part2(input: &Self::Input) -> Self::Output {
+            <Toy as Solver>::part2(input)
+        }
+    }
+    impl Sample for Toy {
+        const SAMPLES: &'static [Example] = &[Example::new("sample", "1\n2").part1("3")];
+    }
+
+    #[derive(Debug)]
+    struct Doubled;
+
+    impl Solver for Toy {
+        type Output = u32;
+        type Input = Vec<u32>;
+        const SHARES_INPUT: bool = true;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.lines().map(|l| parse_at(input, l)).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Output {
+            input.iter().sum()
+        }
+
+        fn part2(_input: &Self::Input) -> Self::Output {
+            panic!("not yet")
+        }
+    }
+
+    impl Solver<Doubled> for Toy {
+        type Output = u32;
+        type Input = Vec<u32>;
+        const SHARES_INPUT: bool = true;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            <Toy as Solver>::parse(input)
+        }
+
+        fn part1(input: &Self::Input) -> Self::Output {
+            input.iter().sum::<u32>() * 2
+        }
+
+        fn part2(input: &Self::Input) -> Self::Output {
+            <Toy as Solver<Doubled>>::part1(input)
+        }
+
+        fn visualize(input: &Self::Input, _part: Part) -> Option<Frames<'_>> {
+            Some(Box::new(input.iter().scan(0, |sum, n| {
+                *sum += n * 2;
+                Some(Frame::new(format!("+{}", n * 2), sum.to_string()))
+            })))
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register::<Toy, Main>();
+        registry.register::<Toy, Doubled>();
+
+        assert_eq!(registry.days(2021), vec![3]);
+        assert_eq!(registry.day(2021, 1).count(), 0);
+        let entries = registry.day(2021, 3).collect::<Vec<_>>();
+        assert_eq!(
+            entries.iter().map(|e| (e.year, e.alt)).collect::<Vec<_>>(),
+            vec![(2021, "Main"), (2021, "Doubled")]
+        );
+
+        let input = entries[0].samples()[0].content;
+        assert_eq!(
+            entries[0].solve(input, Part::One).unwrap().0,
+            Answer::Number(3)
+        );
+        assert!(matches!(
+            entries[0].solve(input, Part::Two),
+            Err(Error::Solve { day: 3, .. })
+        ));
+        assert_eq!(
+            entries[1].solve(input, Part::Two).unwrap().0,
+            Answer::Number(6)
+        );
+
+        // alternates that opt in share the parsed input
+        assert!(entries[1].shares_input(entries[0]));
+        let (parsed, _) = entries[0].parse(input).unwrap();
+        assert_eq!(
+            entries[1].solve_parsed(&parsed, Part::One).unwrap().0,
+            Answer::Number(6)
+        );
+        assert_eq!(
+            entries[0].solve_parsed(&parsed, Part::One).unwrap().0,
+            Answer::Number(3)
+        );
+        assert!(entries[0].visualize(&parsed, Part::One).is_none());
+        assert_eq!(
+            entries[1]
+                .visualize(&parsed, Part::One)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![Frame::new("+2", "2"), Frame::new("+4", "6")]
+        );
+        assert!(matches!(
+            entries[1].solve("1\nx", Part::One),
+            Err(Error::Parse(ParseError {
+                day: Some(3),
+                line: 2,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_registry_check() {
+        let mut registry = Registry::new();
+        registry.register::<Toy, Main>();
+        registry.register::<Toy, Doubled>();
+        assert_eq!(
+            registry.check(None),
+            vec!["2021 day 3 Doubled, sample part One: got 6, expected 3"]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("2021")).unwrap();
+        std::fs::write(dir.path().join("2021/day03.txt"), "4\n5").unwrap();
+        std::fs::write(dir.path().join("2021/day03.answers"), "part2: 18\n").unwrap();
+        let mismatches = registry.check(Some(dir.path()));
+        assert_eq!(mismatches.len(), 2);
+        assert!(mismatches[0].starts_with("2021 day 3 Main, "));
+        assert!(mismatches[0].ends_with("day03.txt part Two: day 3, part Two failed: not yet"));
+    }
+
+    #[test]
+    fn test_registry_merge() {
+        let mut registry = Registry::new();
+        registry.register::<Toy, Main>();
+        let mut other = Registry::new();
+        other.register::<Other, Main>();
+        registry.merge(other);
+
+        assert_eq!(registry.years(), vec![2020, 2021]);
+        assert_eq!(registry.days(2020), vec![3]);
+        assert_eq!(registry.days(2019), Vec::<u8>::new());
+        let entries = registry.day(2020, 3).collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].year, 2020);
+        assert!(!entries[0].shares_input(&registry.entries()[0]));
+        // parts that don't exist aren't solved
+        assert_eq!(entries[0].solve("1", Part::Two).unwrap().0, Answer::None);
+    }
 }
@@ -0,0 +1,116 @@
+use crate::{Input, Part};
+use anyhow::Context;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// An input read from a plain file, with its known answers in a file next to it.
+///
+/// Answers files have one line per known answer, e.g.:
+/// ```text
+/// part1: 1234
+/// part2: 5678
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocalInput {
+    path: PathBuf,
+    answers: PathBuf,
+}
+
+impl LocalInput {
+    /// The input of a day within an inputs directory, e.g. `inputs/2021/day07.txt` with `inputs/2021/day07.answers`
+    pub fn new(dir: impl AsRef<Path>, year: u16, day: u8) -> Self {
+        Self::from_file(
+            dir.as_ref()
+                .join(year.to_string())
+                .join(format!("day{:02}.txt", day)),
+        )
+    }
+
+    /// A single input file, its answers are in the same file with an `answers` extension.
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let answers = path.with_extension("answers");
+        Self { path, answers }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Input for LocalInput {
+    fn load(&mut self) -> anyhow::Result<String> {
+        std::fs::read_to_string(&self.path)
+            .with_context(|| format!("cannot read input {}", self.path.display()))
+    }
+
+    fn solution(&self, part: Part) -> Option<String> {
+        // answers are optional, a missing file just means we don't know them yet
+        let answers = std::fs::read_to_string(&self.answers).ok()?;
+        parse_answer(&answers, part)
+    }
+}
+
+/// An input piped in, e.g. generated or edited by hand, its answers aren't known.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinInput;
+
+impl Input for StdinInput {
+    fn load(&mut self) -> anyhow::Result<String> {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("cannot read input from stdin")?;
+        Ok(input)
+    }
+
+    fn solution(&self, _part: Part) -> Option<String> {
+        None
+    }
+}
+
+fn parse_answer(answers: &str, part: Part) -> Option<String> {
+    let key = match part {
+        Part::One => "part1",
+        Part::Two => "part2",
+    };
+    answers
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, answer)| answer.trim().to_string())
+        .filter(|answer| !answer.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        let answers = "part1: 1234\npart2:\n";
+        assert_eq!(parse_answer(answers, Part::One), Some("1234".to_string()));
+        assert_eq!(parse_answer(answers, Part::Two), None);
+        assert_eq!(
+            parse_answer("part2: abc", Part::Two),
+            Some("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_local_input() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("2021")).unwrap();
+        std::fs::write(dir.path().join("2021/day07.txt"), "16,1,2").unwrap();
+        std::fs::write(dir.path().join("2021/day07.answers"), "part1: 37").unwrap();
+
+        let mut input = LocalInput::new(dir.path(), 2021, 7);
+        assert_eq!(input.load().unwrap(), "16,1,2");
+        assert_eq!(input.solution(Part::One), Some("37".to_string()));
+        assert_eq!(input.solution(Part::Two), None);
+
+        let mut missing = LocalInput::new(dir.path(), 2021, 8);
+        assert!(missing.load().is_err());
+        assert_eq!(missing.solution(Part::One), None);
+    }
+}
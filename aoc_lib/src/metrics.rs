@@ -0,0 +1,184 @@
+//! Counters and histograms solvers record to show how much work they did, e.g. the states a search expanded.
+//!
+//! The runner collects them per part, on the thread the part is solved on. Solvers should count as they go and record
+//! the totals, recording is cheap but not free.
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// The distribution of some values, e.g. the number of states at each step of a search.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub count: u64,
+    pub sum: u64,
+    pub min: u64,
+    pub max: u64,
+    // counts by powers of two: 0, 1, 2..4, 4..8, ...
+    buckets: Vec<u64>,
+}
+
+impl Histogram {
+    pub fn observe(&mut self, value: u64) {
+        if self.count == 0 || value < self.min {
+            self.min = value;
+        }
+        self.max = self.max.max(value);
+        self.count += 1;
+        self.sum += value;
+        let bucket = (u64::BITS - value.leading_zeros()) as usize;
+        if self.buckets.len() <= bucket {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.count.max(1) as f64
+    }
+
+    /// The ranges values fell in, with how many did, ranges without any are skipped.
+    pub fn buckets(&self) -> impl Iterator<Item = (Range<u64>, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(bucket, &count)| match bucket {
+                0 => (0..1, count),
+                _ => (1 << (bucket - 1)..1 << bucket, count),
+            })
+    }
+}
+
+impl Display for Histogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} values, min {}, mean {:.1}, max {}",
+            self.count,
+            self.min,
+            self.mean(),
+            self.max
+        )
+    }
+}
+
+/// The counters and histograms of a part, in the order they were first recorded.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Metrics {
+    pub counters: Vec<(&'static str, u64)>,
+    pub histograms: Vec<(&'static str, Histogram)>,
+}
+
+impl Metrics {
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.histograms.is_empty()
+    }
+
+    fn count(&mut self, name: &'static str, n: u64) {
+        match self
+            .counters
+            .iter_mut()
+            .find(|(counter, _)| *counter == name)
+        {
+            Some((_, total)) => *total += n,
+            None => self.counters.push((name, n)),
+        }
+    }
+
+    fn observe(&mut self, name: &'static str, value: u64) {
+        match self.histograms.iter_mut().find(|(hist, _)| *hist == name) {
+            Some((_, histogram)) => histogram.observe(value),
+            None => {
+                let mut histogram = Histogram::default();
+                histogram.observe(value);
+                self.histograms.push((name, histogram));
+            }
+        }
+    }
+}
+
+/// A summary on one line, e.g.: `states expanded 1234, states per step: 14 values, min 1, mean 20.5, max 300`
+impl Display for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counters = self
+            .counters
+            .iter()
+            .map(|(name, total)| format!("{} {}", name, total));
+        let histograms = self
+            .histograms
+            .iter()
+            .map(|(name, histogram)| format!("{}: {}", name, histogram));
+        let summary = counters.chain(histograms).collect::<Vec<_>>();
+        write!(f, "{}", summary.join(", "))
+    }
+}
+
+// the metrics of the part solved on this thread, when they're collected
+#[thread_local]
+static METRICS: RefCell<Option<Metrics>> = RefCell::new(None);
+
+/// Runs a task and collects the metrics it records on this thread.
+pub fn collect<T>(task: impl FnOnce() -> T) -> (T, Metrics) {
+    let restore = Restore(METRICS.replace(Some(Metrics::default())));
+    let done = task();
+    let metrics = METRICS.borrow_mut().take();
+    drop(restore);
+    (done, metrics.unwrap_or_default())
+}
+
+// puts back what was collected before, even when the task panics
+struct Restore(Option<Metrics>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        METRICS.replace(self.0.take());
+    }
+}
+
+/// Adds to a counter, e.g.: `metrics::count("states expanded", expanded)`
+pub fn count(name: &'static str, n: u64) {
+    if let Some(metrics) = &mut *METRICS.borrow_mut() {
+        metrics.count(name, n);
+    }
+}
+
+/// Adds a value to a histogram, e.g.: `metrics::observe("states per step", states.len() as u64)`
+pub fn observe(name: &'static str, value: u64) {
+    if let Some(metrics) = &mut *METRICS.borrow_mut() {
+        metrics.observe(name, value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let (answer, metrics) = collect(|| {
+            for states in [1, 3, 4, 0] {
+                count("states", states);
+                observe("states per step", states);
+            }
+            count("steps", 4);
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(metrics.counters, vec![("states", 8), ("steps", 4)]);
+        let (name, histogram) = &metrics.histograms[0];
+        assert_eq!(*name, "states per step");
+        assert_eq!((histogram.count, histogram.min, histogram.max), (4, 0, 4));
+        assert_eq!(
+            histogram.buckets().collect::<Vec<_>>(),
+            vec![(0..1, 1), (1..2, 1), (2..4, 1), (4..8, 1)]
+        );
+        assert_eq!(
+            metrics.to_string(),
+            "states 8, steps 4, states per step: 4 values, min 0, mean 2.0, max 4"
+        );
+
+        // nothing is collected outside of `collect`
+        count("states", 1);
+        assert!(collect(|| ()).1.is_empty());
+    }
+}
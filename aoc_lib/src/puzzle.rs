@@ -0,0 +1,171 @@
+//! Reading puzzle descriptions, as cached by aocf: converted from the HTML of the puzzle to markdown by html2md.
+
+/// A sample guessed from a puzzle description, to be checked before it's put in `sample!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub content: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The samples of a description with one section per part, headed e.g. `--- Day 1: Sonar Sweep ---` and
+/// `--- Part Two ---`: each example block is a candidate, the last emphasized answer of a part is taken to be the answer
+/// to its first example, or to the first example of part one.
+pub fn candidates(markdown: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = vec![];
+    for (part, section) in parts(markdown).into_iter().take(2).enumerate() {
+        let mut first = None;
+        for content in examples(&section) {
+            let idx = match candidates.iter().position(|c| c.content == content) {
+                Some(idx) => idx,
+                None => {
+                    candidates.push(Candidate {
+                        content,
+                        part1: None,
+                        part2: None,
+                    });
+                    candidates.len() - 1
+                }
+            };
+            first.get_or_insert(idx);
+        }
+        let answer = answers(&section).pop();
+        if let Some(candidate) = candidates.get_mut(first.unwrap_or(0)) {
+            match part {
+                0 => candidate.part1 = answer,
+                _ => candidate.part2 = answer,
+            }
+        }
+    }
+    candidates
+}
+
+/// The emphasized code of a puzzle description outside of example blocks, answers to the examples are shown that way,
+/// e.g. `` `*7*` `` or `` *`7`* ``.
+pub fn answers(markdown: &str) -> Vec<String> {
+    let mut answers = vec![];
+    for line in prose(markdown) {
+        // code spans are every other segment
+        let segments = line.split('`').collect::<Vec<_>>();
+        for idx in (1..segments.len().saturating_sub(1)).step_by(2) {
+            let code = segments[idx];
+            if let Some(answer) = code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                answers.push(answer.to_string());
+            } else if segments[idx - 1].ends_with('*') && segments[idx + 1].starts_with('*') {
+                answers.push(code.to_string());
+            }
+        }
+    }
+    answers
+}
+
+// the sections of a description after each of its headings, or the whole description if it has none
+fn parts(markdown: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut part = None;
+    let mut in_block = false;
+    for line in markdown.lines() {
+        in_block ^= line.starts_with("```");
+        let title = line.trim_start_matches('\\');
+        if !in_block && title.starts_with("--- ") && title.ends_with(" ---") {
+            parts.extend(part.replace(String::new()));
+        } else if let Some(part) = &mut part {
+            part.push_str(line);
+            part.push('\n');
+        }
+    }
+    parts.extend(part);
+    if parts.is_empty() {
+        vec![markdown.to_string()]
+    } else {
+        parts
+    }
+}
+
+// the lines of a description outside of example blocks
+fn prose(markdown: &str) -> impl Iterator<Item = &str> {
+    let mut in_block = false;
+    markdown.lines().filter(move |line| {
+        let fence = line.starts_with("```");
+        in_block ^= fence;
+        !fence && !in_block
+    })
+}
+
+/// The example blocks of a puzzle description, in order, as plain text.
+pub fn examples(markdown: &str) -> Vec<String> {
+    let mut examples = vec![];
+    let mut block: Option<Vec<&str>> = None;
+    for line in markdown.lines() {
+        match (line.starts_with("```"), &mut block) {
+            (true, None) => block = Some(vec![]),
+            (true, Some(lines)) => {
+                examples.push(lines.join("\n").trim_end_matches('\n').to_string());
+                block = None;
+            }
+            (false, Some(lines)) => lines.push(line),
+            (false, None) => {}
+        }
+    }
+    examples
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let markdown =
+            "For example:\n\n```\n199\n200\n208\n\n```\n\nThen:\n\n```\na -> b &lt;\n\n```\n";
+        assert_eq!(examples(markdown), vec!["199\n200\n208", "a -> b &lt;"]);
+        assert!(examples("No examples").is_empty());
+    }
+
+    // the way aocf caches a description, see the fixture of the scaffold tests for a whole one
+    const DESCRIPTION: &str = "\\--- Day 1: Sonar Sweep ---\n----------\n\n\
+        For example, suppose you had the following report:\n\n\
+        ```\n199\n200\n208\n210\n\n```\n\n\
+        In this example, there are `*7*` measurements that are larger than the previous measurement.\n\n\
+        Your puzzle answer was `1390`.\n\n\
+        \\--- Part Two ---\n----------\n\n\
+        ```\nA: 607 (N/A - no previous sum)\n\n```\n\n\
+        In this example, there are *`5`* sums that are larger than the previous sum.\n";
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(answers(DESCRIPTION), vec!["7", "5"]);
+        assert_eq!(answers("```\n`*1*`\n```"), Vec::<String>::new());
+        assert_eq!(answers("`*a_b**` and `*`"), vec!["a_b*"]);
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(
+            candidates(DESCRIPTION),
+            vec![
+                Candidate {
+                    content: "199\n200\n208\n210".to_string(),
+                    part1: Some("7".to_string()),
+                    part2: None,
+                },
+                Candidate {
+                    content: "A: 607 (N/A - no previous sum)".to_string(),
+                    part1: None,
+                    part2: Some("5".to_string()),
+                }
+            ]
+        );
+
+        // part two often reuses the example of part one
+        let reused = "--- Day 3 ---\n```\n1\n```\n`*2*`\n--- Part Two ---\n`*3*`\n";
+        assert_eq!(
+            candidates(reused),
+            vec![Candidate {
+                content: "1".to_string(),
+                part1: Some("2".to_string()),
+                part2: Some("3".to_string()),
+            }]
+        );
+    }
+}
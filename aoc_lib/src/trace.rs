@@ -0,0 +1,200 @@
+//! Named events solvers emit while they run, e.g. each "explode" of day 18 with the number it left, see `trace!`.
+//!
+//! The runner records them per parse and part, on the thread the task runs on. Otherwise they are logged at debug level.
+use std::cell::RefCell;
+use std::fmt::Display;
+
+/// The value of a field of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t: ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                Value::Int(n as i64)
+            }
+        })*
+    };
+}
+
+from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Float(x)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        Value::Text(c.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Text(s)
+    }
+}
+
+/// Something that happened while solving, e.g. `explode` with the number it left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub kind: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        for (name, value) in &self.fields {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// The kinds of events to record, all of them when empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub kinds: Vec<String>,
+}
+
+impl Filter {
+    fn accepts(&self, kind: &str) -> bool {
+        self.kinds.is_empty() || self.kinds.iter().any(|k| k == kind)
+    }
+}
+
+// the events of the task running on this thread, when it's recorded
+#[thread_local]
+static RECORDING: RefCell<Option<(Filter, Vec<Event>)>> = RefCell::new(None);
+
+/// Runs a task and collects the events it emits on this thread that pass the filter, none without a filter.
+pub fn record<T>(filter: Option<&Filter>, task: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let filter = match filter {
+        Some(filter) => filter.clone(),
+        None => return (task(), vec![]),
+    };
+    let restore = Restore(RECORDING.replace(Some((filter, vec![]))));
+    let done = task();
+    let events = RECORDING.borrow_mut().take();
+    drop(restore);
+    (done, events.map_or(vec![], |(_, events)| events))
+}
+
+// puts back what was recorded before, even when the task panics
+struct Restore(Option<(Filter, Vec<Event>)>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        RECORDING.replace(self.0.take());
+    }
+}
+
+/// Whether an event of a kind would go anywhere, so that its fields are only worked out when needed.
+pub fn enabled(target: &str, kind: &str) -> bool {
+    match &*RECORDING.borrow() {
+        Some((filter, _)) => filter.accepts(kind),
+        None => log::log_enabled!(target: target, log::Level::Debug),
+    }
+}
+
+/// Records an event, or logs it from `target` when nothing records, see `trace!`.
+pub fn emit(target: &str, event: Event) {
+    match &mut *RECORDING.borrow_mut() {
+        Some((filter, events)) => {
+            if filter.accepts(event.kind) {
+                events.push(event);
+            }
+        }
+        None => log::debug!(target: target, "{}", event),
+    }
+}
+
+/// Emits an event with named fields, the values are anything a `Value` converts from and are only evaluated when the
+/// event is recorded or logged, e.g.: `trace!("fold", axis = 'x', at = 655, dots = dots.len())`
+#[macro_export]
+macro_rules! trace {
+    ($kind: literal $(, $name: ident = $value: expr)* $(,)?) => {
+        if $crate::trace::enabled(module_path!(), $kind) {
+            $crate::trace::emit(
+                module_path!(),
+                $crate::trace::Event {
+                    kind: $kind,
+                    fields: vec![$((stringify!($name), $crate::trace::Value::from($value))),*],
+                },
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solve(n: usize) -> usize {
+        for i in 0..n {
+            trace!("step", i = i, even = i % 2 == 0);
+        }
+        trace!("done", answer = format!("{} steps", n));
+        n
+    }
+
+    #[test]
+    fn test_record() {
+        let (answer, events) = record(Some(&Filter::default()), || solve(2));
+        assert_eq!(answer, 2);
+        assert_eq!(
+            events.iter().map(Event::to_string).collect::<Vec<_>>(),
+            vec![
+                "step i=0 even=true",
+                "step i=1 even=false",
+                "done answer=2 steps"
+            ]
+        );
+
+        let filter = Filter {
+            kinds: vec!["done".to_string()],
+        };
+        let (_, events) = record(Some(&filter), || solve(3));
+        assert_eq!(
+            events,
+            vec![Event {
+                kind: "done",
+                fields: vec![("answer", Value::Text("3 steps".to_string()))],
+            }]
+        );
+
+        assert!(record(None, || solve(1)).1.is_empty());
+    }
+}